@@ -15,3 +15,16 @@ export async function enable(): Promise<void> {
 export async function disable(): Promise<void> {
   await invoke('plugin:autostart|disable')
 }
+
+/**
+ * Whether the app was launched by its autostart entry, detected from the autostart arguments
+ * configured on the plugin builder.
+ *
+ * The plugin also emits the `autostart://launched` event once the app is ready in that case,
+ * this can be called any time later instead of listening to it.
+ *
+ * @since 2.6.0
+ */
+export async function launchedAtStartup(): Promise<boolean> {
+  return await invoke('plugin:autostart|launched_at_startup')
+}
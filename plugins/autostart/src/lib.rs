@@ -15,13 +15,17 @@ use serde::{ser::Serializer, Serialize};
 use tauri::{
     command,
     plugin::{Builder as PluginBuilder, TauriPlugin},
-    Manager, Runtime, State,
+    Emitter, Manager, RunEvent, Runtime, State,
 };
 
-use std::env::current_exe;
+use std::{env::current_exe, ffi::OsString};
 
 type Result<T> = std::result::Result<T, Error>;
 
+/// Event emitted once the app is ready when it was launched by its autostart entry,
+/// see [`AutoLaunchManager::launched_at_startup`].
+pub const LAUNCHED_EVENT: &str = "autostart://launched";
+
 #[derive(Debug, Default, Copy, Clone)]
 pub enum MacosLauncher {
     #[default]
@@ -46,29 +50,61 @@ impl Serialize for Error {
     }
 }
 
-pub struct AutoLaunchManager(AutoLaunch);
+pub struct AutoLaunchManager {
+    auto_launch: AutoLaunch,
+    launched_at_startup: bool,
+}
 
 impl AutoLaunchManager {
     pub fn enable(&self) -> Result<()> {
-        self.0
+        self.auto_launch
             .enable()
             .map_err(|e| e.to_string())
             .map_err(Error::Anyhow)
     }
 
     pub fn disable(&self) -> Result<()> {
-        self.0
+        self.auto_launch
             .disable()
             .map_err(|e| e.to_string())
             .map_err(Error::Anyhow)
     }
 
     pub fn is_enabled(&self) -> Result<bool> {
-        self.0
+        self.auto_launch
             .is_enabled()
             .map_err(|e| e.to_string())
             .map_err(Error::Anyhow)
     }
+
+    /// Whether the app was launched by its autostart entry.
+    ///
+    /// This is detected by looking for the [autostart arguments](Builder::args) in the process arguments,
+    /// so it is always `false` if no argument was configured: the OS starts the app the same way
+    /// as a manual launch otherwise. Pass a dedicated argument such as `--autostart` that the app
+    /// is not launched with otherwise, as launching the app manually with the same arguments
+    /// can't be told apart from an autostart.
+    ///
+    /// The [`LAUNCHED_EVENT`] is emitted when the app is ready in that case, this can be read
+    /// any time later, e.g. by a webview that was not listening yet.
+    pub fn launched_at_startup(&self) -> bool {
+        self.launched_at_startup
+    }
+}
+
+/// Whether `args`, the process arguments including the executable, contain the arguments of the autostart entry.
+fn has_autostart_args(autostart_args: &[String], args: &[OsString]) -> bool {
+    !autostart_args.is_empty()
+        && args
+            .get(1..)
+            .unwrap_or_default()
+            .windows(autostart_args.len())
+            .any(|window| {
+                window
+                    .iter()
+                    .zip(autostart_args)
+                    .all(|(arg, autostart_arg)| arg == autostart_arg.as_str())
+            })
 }
 
 pub trait ManagerExt<R: Runtime> {
@@ -98,6 +134,11 @@ async fn is_enabled(manager: State<'_, AutoLaunchManager>) -> Result<bool> {
     manager.is_enabled()
 }
 
+#[command]
+fn launched_at_startup(manager: State<'_, AutoLaunchManager>) -> bool {
+    manager.launched_at_startup()
+}
+
 #[derive(Default)]
 pub struct Builder {
     #[cfg(target_os = "macos")]
@@ -171,7 +212,12 @@ impl Builder {
 
     pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
         PluginBuilder::new("autostart")
-            .invoke_handler(tauri::generate_handler![enable, disable, is_enabled])
+            .invoke_handler(tauri::generate_handler![
+                enable,
+                disable,
+                is_enabled,
+                launched_at_startup
+            ])
             .setup(move |app, _api| {
                 let mut builder = AutoLaunchBuilder::new();
 
@@ -222,11 +268,23 @@ impl Builder {
                     builder.set_app_path(&current_exe.display().to_string());
                 }
 
-                app.manage(AutoLaunchManager(
-                    builder.build().map_err(|e| e.to_string())?,
-                ));
+                // the arguments are not necessarily valid UTF-8
+                let args: Vec<OsString> = std::env::args_os().collect();
+                app.manage(AutoLaunchManager {
+                    auto_launch: builder.build().map_err(|e| e.to_string())?,
+                    launched_at_startup: has_autostart_args(&self.args, &args),
+                });
                 Ok(())
             })
+            .on_event(|app, event| {
+                // emitted once the app is ready rather than during the setup, before the event loop runs
+                if let RunEvent::Ready = event {
+                    if app.autolaunch().launched_at_startup() {
+                        // an empty payload can't fail to serialize
+                        let _ = app.emit(LAUNCHED_EVENT, ());
+                    }
+                }
+            })
             .build()
     }
 }
@@ -248,3 +306,66 @@ pub fn init<R: Runtime>(
     }
     builder.build()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use super::has_autostart_args;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn os_args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn detects_the_autostart_args() {
+        let autostart_args = args(&["--autostart", "--minimized"]);
+
+        assert!(has_autostart_args(
+            &autostart_args,
+            &os_args(&["app", "--autostart", "--minimized"])
+        ));
+        assert!(has_autostart_args(
+            &autostart_args,
+            &os_args(&["app", "--verbose", "--autostart", "--minimized"])
+        ));
+
+        assert!(!has_autostart_args(&autostart_args, &os_args(&["app"])));
+        assert!(!has_autostart_args(
+            &autostart_args,
+            &os_args(&["app", "--autostart"])
+        ));
+        assert!(!has_autostart_args(
+            &autostart_args,
+            &os_args(&["app", "--minimized", "--autostart"])
+        ));
+        // the executable itself is not an argument
+        assert!(!has_autostart_args(
+            &args(&["--autostart"]),
+            &os_args(&["--autostart"])
+        ));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn ignores_arguments_that_are_not_utf8() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let args = vec![
+            OsString::from("app"),
+            OsString::from_vec(vec![0xff, 0xfe]),
+            OsString::from("--autostart"),
+        ];
+        assert!(has_autostart_args(&["--autostart".into()], &args));
+    }
+
+    #[test]
+    fn cannot_detect_without_autostart_args() {
+        assert!(!has_autostart_args(&[], &os_args(&["app"])));
+        assert!(!has_autostart_args(&[], &os_args(&["app", "--autostart"])));
+    }
+}
@@ -83,6 +83,7 @@ const COMMANDS: &[(&str, &[&str])] = &[
     ("copy_file", &[]),
     ("remove", &[]),
     ("rename", &[]),
+    ("move_into", &[]),
     ("truncate", &[]),
     ("ftruncate", &[]),
     ("write", &[]),
@@ -90,6 +91,7 @@ const COMMANDS: &[(&str, &[&str])] = &[
     ("write_text_file", &[]),
     ("read_dir", &[]),
     ("read_file", &[]),
+    ("read_file_with_stat", &[]),
     ("read", &[]),
     ("open", &[]),
     ("read_text_file", &[]),
@@ -100,6 +102,14 @@ const COMMANDS: &[(&str, &[&str])] = &[
     ("lstat", &[]),
     ("fstat", &[]),
     ("exists", &[]),
+    ("access", &[]),
+    ("realpath", &[]),
+    ("get_xattr", &[]),
+    ("set_xattr", &[]),
+    ("list_xattr", &[]),
+    ("list_trash", &[]),
+    ("restore_from_trash", &[]),
+    ("files_equal", &[]),
     ("watch", &[]),
     // TODO: Remove this in v3
     ("unwatch", &[]),
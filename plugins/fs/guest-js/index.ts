@@ -586,6 +586,13 @@ interface CopyFileOptions {
   fromPathBaseDir?: BaseDirectory
   /** Base directory for `toPath`. */
   toPathBaseDir?: BaseDirectory
+  /**
+   * Whether to copy the file a symlink points to. When `false` and `fromPath` is a symlink,
+   * the symlink itself is recreated at `toPath`. Defaults to `true`.
+   *
+   * @since 2.5.0
+   */
+  followSymlinks?: boolean
 }
 
 /**
@@ -661,6 +668,13 @@ async function mkdir(
 interface ReadDirOptions {
   /** Base directory for `path` */
   baseDir?: BaseDirectory
+  /**
+   * Whether `isFile` and `isDirectory` describe what the symlinks point to rather than the symlinks themselves.
+   * `isSymlink` is set either way. Symlinks pointing out of the scope are described as is. Defaults to `false`.
+   *
+   * @since 2.5.0
+   */
+  followSymlinks?: boolean
 }
 
 /**
@@ -725,6 +739,26 @@ interface ReadFileOptions {
   baseDir?: BaseDirectory
 }
 
+/**
+ * Progress of a {@linkcode readFile}.
+ *
+ * @since 2.5.0
+ */
+interface ReadFileProgress {
+  /** Number of bytes read so far. */
+  read: number
+  /** Length of the file. */
+  total: number
+}
+
+/**
+ * @since 2.5.0
+ */
+interface ReadFileProgressOptions extends ReadFileOptions {
+  /** Called as the file is read in chunks. */
+  onProgress?: (progress: ReadFileProgress) => void
+}
+
 /**
  * Reads and resolves to the entire contents of a file as an array of bytes.
  * TextDecoder can be used to transform the bytes to string if required.
@@ -732,26 +766,84 @@ interface ReadFileOptions {
  * ```typescript
  * import { readFile, BaseDirectory } from '@tauri-apps/plugin-fs';
  * const contents = await readFile('avatar.png', { baseDir: BaseDirectory.Resource });
+ * const video = await readFile('video.mp4', {
+ *   baseDir: BaseDirectory.Video,
+ *   onProgress: ({ read, total }) => console.log(`${read} of ${total} bytes read`)
+ * });
  * ```
  *
  * @since 2.0.0
  */
 async function readFile(
   path: string | URL,
-  options?: ReadFileOptions
+  options?: ReadFileProgressOptions
 ): Promise<Uint8Array<ArrayBuffer>> {
   if (path instanceof URL && path.protocol !== 'file:') {
     throw new TypeError('Must be a file URL.')
   }
 
+  let onProgress: Channel<ReadFileProgress> | undefined
+  if (options?.onProgress) {
+    onProgress = new Channel<ReadFileProgress>()
+    onProgress.onmessage = options.onProgress
+  }
+
   const arr = await invoke<ArrayBuffer | number[]>('plugin:fs|read_file', {
     path: path instanceof URL ? path.toString() : path,
-    options
+    options: options && { baseDir: options.baseDir },
+    onProgress
   })
 
   return arr instanceof ArrayBuffer ? new Uint8Array(arr) : Uint8Array.from(arr)
 }
 
+/**
+ * Reads the entire contents of a file along with its metadata.
+ *
+ * The metadata is read from the same open file as the contents,
+ * so it can't describe a different file replacing it in the meantime.
+ *
+ * @example
+ * ```typescript
+ * import { readFileWithStat, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const { contents, info } = await readFileWithStat('notes.md', { baseDir: BaseDirectory.AppData });
+ * console.log(info.mtime);
+ * ```
+ *
+ * @since 2.5.0
+ */
+async function readFileWithStat(
+  path: string | URL,
+  options?: ReadFileOptions
+): Promise<{ contents: Uint8Array<ArrayBuffer>; info: FileInfo }> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const arr = await invoke<ArrayBuffer | number[]>(
+    'plugin:fs|read_file_with_stat',
+    {
+      path: path instanceof URL ? path.toString() : path,
+      options
+    }
+  )
+
+  const bytes =
+    arr instanceof ArrayBuffer ? new Uint8Array(arr) : Uint8Array.from(arr)
+
+  // Rust side appends the serialized file info and its length (as bigendian bytes)
+  // at the end of returned array to avoid serialization overhead of the contents.
+  const infoLen = fromBytes(
+    Array.from(bytes.slice(-8)) as FixedSizeArray<number, 8>
+  )
+  const infoStart = bytes.byteLength - 8 - infoLen
+  const info = JSON.parse(
+    new TextDecoder().decode(bytes.slice(infoStart, -8))
+  ) as UnparsedFileInfo
+
+  return { contents: bytes.slice(0, infoStart), info: parseFileInfo(info) }
+}
+
 /**
  * Reads and returns the entire contents of a file as UTF-8 string.
  * @example
@@ -932,6 +1024,68 @@ async function rename(
   })
 }
 
+/**
+ * @since 2.5.0
+ */
+interface MoveIntoOptions {
+  /** Base directory for `paths`. */
+  pathsBaseDir?: BaseDirectory
+  /** Base directory for `destDir`. */
+  destDirBaseDir?: BaseDirectory
+  /**
+   * Replace entries in `destDir` that have the same name.
+   * When `false`, a ` (n)` suffix is added to the moved entry's name instead. Defaults to `false`.
+   */
+  overwrite?: boolean
+}
+
+/**
+ * The outcome of moving a single path with {@linkcode moveInto}.
+ *
+ * @since 2.5.0
+ */
+interface MoveIntoResult {
+  /** The path that was requested to be moved. */
+  source: string
+  /** The new path of the entry, or `null` if it could not be moved. */
+  destination: string | null
+  /** The reason the entry could not be moved. */
+  error: string | null
+}
+
+/**
+ * Moves files or directories into `destDir`, keeping their names.
+ * Entries are moved one by one, a failure to move one of them does not stop the others.
+ *
+ * If `destDir` is on a different device, the entries are copied and then removed.
+ *
+ * @example
+ * ```typescript
+ * import { moveInto, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const results = await moveInto(['a.png', 'b.png'], 'archive', { pathsBaseDir: BaseDirectory.AppData, destDirBaseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.5.0
+ */
+async function moveInto(
+  paths: Array<string | URL>,
+  destDir: string | URL,
+  options?: MoveIntoOptions
+): Promise<MoveIntoResult[]> {
+  if (
+    paths.some((path) => path instanceof URL && path.protocol !== 'file:')
+    || (destDir instanceof URL && destDir.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|move_into', {
+    paths: paths.map((path) => (path instanceof URL ? path.toString() : path)),
+    destDir: destDir instanceof URL ? destDir.toString() : destDir,
+    options
+  })
+}
+
 /**
  * @since 2.0.0
  */
@@ -1049,6 +1203,13 @@ interface WriteFileOptions {
   mode?: number
   /** Base directory for `path` */
   baseDir?: BaseDirectory
+  /**
+   * Defaults to `false`. If set to `true`, waits for the contents to be flushed to the storage device before returning.
+   * Not supported when writing a `ReadableStream`.
+   *
+   * @since 2.5.0
+   */
+  sync?: boolean
 }
 
 /**
@@ -1165,6 +1326,277 @@ async function exists(
   })
 }
 
+/**
+ * @since 2.5.0
+ */
+interface AccessOptions {
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+  /** Check whether the path can be read. */
+  read?: boolean
+  /** Check whether the path can be written to. */
+  write?: boolean
+  /** Check whether the path can be executed, or traversed for directories. */
+  exec?: boolean
+}
+
+/**
+ * The result of {@linkcode access}, checks that were not requested are `false`.
+ *
+ * @since 2.5.0
+ */
+interface AccessInfo {
+  exists: boolean
+  isFile: boolean
+  isDir: boolean
+  readable: boolean
+  writable: boolean
+  executable: boolean
+}
+
+/**
+ * Check if a path exists, what it is and whether the current process can access it.
+ * @example
+ * ```typescript
+ * import { access, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * // Check if `$APPDATA/avatar.png` can be overwritten
+ * const { exists, isFile, writable } = await access('avatar.png', { baseDir: BaseDirectory.AppData, write: true });
+ * ```
+ *
+ * @since 2.5.0
+ */
+async function access(
+  path: string | URL,
+  options?: AccessOptions
+): Promise<AccessInfo> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|access', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * @since 2.5.0
+ */
+interface RealpathOptions {
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Returns the canonical, absolute form of a path, with all symlinks and `.`/`..` components resolved.
+ *
+ * Fails if the path does not exist or if the resolved path is outside of the scope,
+ * e.g. when it is a symlink to a file that is not allowed.
+ * @example
+ * ```typescript
+ * import { realpath, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * // Resolve the file `$APPDATA/current` links to
+ * const path = await realpath('current', { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.5.0
+ */
+async function realpath(
+  path: string | URL,
+  options?: RealpathOptions
+): Promise<string> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|realpath', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * @since 2.5.0
+ */
+interface FilesEqualOptions {
+  /** Base directory for `a`. */
+  aBaseDir?: BaseDirectory
+  /** Base directory for `b`. */
+  bBaseDir?: BaseDirectory
+}
+
+/**
+ * Checks whether two files have the same contents.
+ *
+ * The files are compared chunk by chunk, stopping at the first difference, and without reading them when their sizes differ.
+ * @example
+ * ```typescript
+ * import { filesEqual, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const unchanged = await filesEqual('app.conf', 'app.conf.bk', { aBaseDir: BaseDirectory.AppConfig, bBaseDir: BaseDirectory.AppConfig });
+ * ```
+ *
+ * @since 2.5.0
+ */
+async function filesEqual(
+  a: string | URL,
+  b: string | URL,
+  options?: FilesEqualOptions
+): Promise<boolean> {
+  if (
+    (a instanceof URL && a.protocol !== 'file:')
+    || (b instanceof URL && b.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|files_equal', {
+    a: a instanceof URL ? a.toString() : a,
+    b: b instanceof URL ? b.toString() : b,
+    options
+  })
+}
+
+/**
+ * @since 2.5.0
+ */
+interface XattrOptions {
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Reads an extended attribute of a file, or `null` if it is not set.
+ *
+ * Extended attributes are stored as xattrs on Unix and as alternate data streams on Windows.
+ * Fails if the platform or the file system doesn't support them.
+ * @example
+ * ```typescript
+ * import { getXattr, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const tag = await getXattr('avatar.png', 'user.tag', { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.5.0
+ */
+async function getXattr(
+  path: string | URL,
+  name: string,
+  options?: XattrOptions
+): Promise<Uint8Array | null> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const value = await invoke<number[] | null>('plugin:fs|get_xattr', {
+    path: path instanceof URL ? path.toString() : path,
+    name,
+    options
+  })
+
+  return value ? Uint8Array.from(value) : null
+}
+
+/**
+ * Sets an extended attribute of a file, replacing its value if it is already set.
+ *
+ * On Linux, attributes set by apps must be in the `user.` namespace.
+ * @example
+ * ```typescript
+ * import { setXattr, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await setXattr('avatar.png', 'user.tag', new TextEncoder().encode('profile'), { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.5.0
+ */
+async function setXattr(
+  path: string | URL,
+  name: string,
+  value: Uint8Array,
+  options?: XattrOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|set_xattr', {
+    path: path instanceof URL ? path.toString() : path,
+    name,
+    value: Array.from(value),
+    options
+  })
+}
+
+/**
+ * Lists the names of the extended attributes of a file.
+ * @example
+ * ```typescript
+ * import { listXattr, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const names = await listXattr('avatar.png', { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.5.0
+ */
+async function listXattr(
+  path: string | URL,
+  options?: XattrOptions
+): Promise<string[]> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|list_xattr', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * An item in the trash.
+ *
+ * @since 2.5.0
+ */
+interface TrashItem {
+  /** Platform specific identifier of the item, to restore it with {@linkcode restoreFromTrash}. */
+  id: string
+  /** Path of the item before it was moved to the trash. */
+  originalPath: string
+  /** When the item was moved to the trash, in seconds since the Unix epoch. */
+  deletedAt: number
+}
+
+/**
+ * Lists the items in the trash whose original path is allowed by the scope.
+ *
+ * Only supported on Windows and Linux with the `trash` feature of the Rust plugin enabled, fails otherwise.
+ * @example
+ * ```typescript
+ * import { listTrash } from '@tauri-apps/plugin-fs';
+ * const items = await listTrash();
+ * ```
+ *
+ * @since 2.5.0
+ */
+async function listTrash(): Promise<TrashItem[]> {
+  return await invoke('plugin:fs|list_trash')
+}
+
+/**
+ * Moves an item of the trash back to its original path, which must be allowed by the scope
+ * and must not exist.
+ *
+ * Only supported on Windows and Linux with the `trash` feature of the Rust plugin enabled, fails otherwise.
+ * @example
+ * ```typescript
+ * import { listTrash, restoreFromTrash } from '@tauri-apps/plugin-fs';
+ * const [item] = await listTrash();
+ * await restoreFromTrash(item.id);
+ * ```
+ *
+ * @since 2.5.0
+ */
+async function restoreFromTrash(id: string): Promise<void> {
+  await invoke('plugin:fs|restore_from_trash', { id })
+}
+
 /**
  * @since 2.0.0
  */
@@ -1321,6 +1753,19 @@ async function watchImmediate(
   })
 }
 
+/**
+ * @since 2.5.0
+ */
+interface SizeOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+  /**
+   * Whether to count the files and directories the symlinks inside of the directory point to.
+   * Symlinks pointing out of the scope are not followed. Defaults to `false`.
+   */
+  followSymlinks?: boolean
+}
+
 /**
  * Get the size of a file or directory. For files, the `stat` functions can be used as well.
  *
@@ -1336,13 +1781,17 @@ async function watchImmediate(
  *
  * @since 2.1.0
  */
-async function size(path: string | URL): Promise<number> {
+async function size(
+  path: string | URL,
+  options?: SizeOptions
+): Promise<number> {
   if (path instanceof URL && path.protocol !== 'file:') {
     throw new TypeError('Must be a file URL.')
   }
 
   return await invoke('plugin:fs|size', {
-    path: path instanceof URL ? path.toString() : path
+    path: path instanceof URL ? path.toString() : path,
+    options
   })
 }
 
@@ -1353,13 +1802,24 @@ export type {
   MkdirOptions,
   DirEntry,
   ReadDirOptions,
+  SizeOptions,
   ReadFileOptions,
+  ReadFileProgressOptions,
+  ReadFileProgress,
   RemoveOptions,
   RenameOptions,
+  MoveIntoOptions,
+  MoveIntoResult,
   StatOptions,
   TruncateOptions,
   WriteFileOptions,
   ExistsOptions,
+  AccessOptions,
+  AccessInfo,
+  RealpathOptions,
+  FilesEqualOptions,
+  XattrOptions,
+  TrashItem,
   FileInfo,
   WatchOptions,
   DebouncedWatchOptions,
@@ -1381,10 +1841,12 @@ export {
   mkdir,
   readDir,
   readFile,
+  readFileWithStat,
   readTextFile,
   readTextFileLines,
   remove,
   rename,
+  moveInto,
   SeekMode,
   stat,
   lstat,
@@ -1392,6 +1854,14 @@ export {
   writeFile,
   writeTextFile,
   exists,
+  access,
+  realpath,
+  getXattr,
+  setXattr,
+  listXattr,
+  listTrash,
+  restoreFromTrash,
+  filesEqual,
   watch,
   watchImmediate,
   size
@@ -6,7 +6,7 @@
 use serde::{Deserialize, Serialize, Serializer};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use tauri::{
-    ipc::{CommandScope, GlobalScope},
+    ipc::{CommandScope, GlobalScope, JavaScriptChannelId},
     path::BaseDirectory,
     utils::config::FsScope,
     Manager, Resource, ResourceId, Runtime, Webview,
@@ -14,6 +14,7 @@ use tauri::{
 
 use std::{
     borrow::Cow,
+    collections::HashSet,
     fs::File,
     io::{BufRead, BufReader, Read, Write},
     path::{Path, PathBuf},
@@ -155,6 +156,8 @@ pub fn open<R: Runtime>(
 pub struct CopyFileOptions {
     from_path_base_dir: Option<BaseDirectory>,
     to_path_base_dir: Option<BaseDirectory>,
+    /// Copy the target of a symlink rather than recreating the symlink, defaults to `true`.
+    follow_symlinks: Option<bool>,
 }
 
 #[tauri::command]
@@ -180,7 +183,11 @@ pub async fn copy_file<R: Runtime>(
         to_path,
         options.as_ref().and_then(|o| o.to_path_base_dir),
     )?;
-    std::fs::copy(&resolved_from_path, &resolved_to_path).map_err(|e| {
+    let follow_symlinks = options
+        .as_ref()
+        .and_then(|o| o.follow_symlinks)
+        .unwrap_or(true);
+    copy_path(&resolved_from_path, &resolved_to_path, follow_symlinks).map_err(|e| {
         format!(
             "failed to copy file from path: {}, to path: {} with error: {e}",
             resolved_from_path.display(),
@@ -190,6 +197,34 @@ pub async fn copy_file<R: Runtime>(
     Ok(())
 }
 
+/// Copies `from` to `to` like [`std::fs::copy`], or recreates the symlink `from` at `to` without `follow_symlinks`.
+///
+/// The recreated symlink points to the same target, a relative target is kept relative to the new link.
+fn copy_path(from: &Path, to: &Path, follow_symlinks: bool) -> std::io::Result<()> {
+    if follow_symlinks || !from.is_symlink() {
+        return std::fs::copy(from, to).map(|_| ());
+    }
+
+    let target = std::fs::read_link(from)?;
+    // replace an existing file like `std::fs::copy` does
+    if std::fs::symlink_metadata(to).is_ok_and(|metadata| !metadata.is_dir()) {
+        std::fs::remove_file(to)?;
+    }
+
+    #[cfg(windows)]
+    {
+        if from.is_dir() {
+            std::os::windows::fs::symlink_dir(target, to)
+        } else {
+            std::os::windows::fs::symlink_file(target, to)
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        std::os::unix::fs::symlink(target, to)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct MkdirOptions {
     #[serde(flatten)]
@@ -246,21 +281,38 @@ pub struct DirEntry {
     pub is_symlink: bool,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadDirOptions {
+    #[serde(flatten)]
+    base: BaseOptions,
+    /// Report whether the target of a symlink is a file or a directory, defaults to `false`.
+    ///
+    /// Symlinks whose target is not allowed by the scope are reported as is.
+    follow_symlinks: Option<bool>,
+}
+
 #[tauri::command]
 pub async fn read_dir<R: Runtime>(
     webview: Webview<R>,
     global_scope: GlobalScope<Entry>,
     command_scope: CommandScope<Entry>,
     path: SafeFilePath,
-    options: Option<BaseOptions>,
+    options: Option<ReadDirOptions>,
 ) -> CommandResult<Vec<DirEntry>> {
     let resolved_path = resolve_path(
         &webview,
         &global_scope,
         &command_scope,
         path,
-        options.as_ref().and_then(|o| o.base_dir),
+        options.as_ref().and_then(|o| o.base.base_dir),
     )?;
+    let follow_symlinks = options
+        .as_ref()
+        .and_then(|o| o.follow_symlinks)
+        .unwrap_or(false);
+    let is_allowed =
+        |path: &Path| is_in_scope(&webview, &global_scope, &command_scope, path.to_path_buf());
 
     let entries = std::fs::read_dir(&resolved_path).map_err(|e| {
         format!(
@@ -274,6 +326,18 @@ pub async fn read_dir<R: Runtime>(
             let entry = entry.ok()?;
             let name = entry.file_name().into_string().ok()?;
             let metadata = entry.file_type();
+            let is_symlink = metadata.as_ref().is_ok_and(|m| m.is_symlink());
+            let metadata = if is_symlink && follow_symlinks {
+                // a broken symlink or one leading out of the scope is reported as is
+                match allowed_symlink_target(&entry.path(), is_allowed) {
+                    Some(target) => std::fs::metadata(target)
+                        .map(|m| m.file_type())
+                        .or(metadata),
+                    None => metadata,
+                }
+            } else {
+                metadata
+            };
             macro_rules! method_or_false {
                 ($method:ident) => {
                     if let Ok(metadata) = &metadata {
@@ -287,7 +351,7 @@ pub async fn read_dir<R: Runtime>(
                 name,
                 is_file: method_or_false!(is_file),
                 is_directory: method_or_false!(is_dir),
-                is_symlink: method_or_false!(is_symlink),
+                is_symlink,
             })
         })
         .collect();
@@ -338,6 +402,7 @@ pub async fn read_file<R: Runtime>(
     command_scope: CommandScope<Entry>,
     path: SafeFilePath,
     options: Option<BaseOptions>,
+    on_progress: Option<JavaScriptChannelId>,
 ) -> CommandResult<tauri::ipc::Response> {
     let (mut file, path) = resolve_file(
         &webview,
@@ -355,9 +420,19 @@ pub async fn read_file<R: Runtime>(
         },
     )?;
 
-    let mut contents = Vec::new();
-
-    file.read_to_end(&mut contents).map_err(|e| {
+    let contents = match on_progress {
+        Some(on_progress) => {
+            let on_progress = on_progress.channel_on(webview);
+            read_with_progress(&mut file, |read, total| {
+                let _ = on_progress.send(ReadProgress { read, total });
+            })
+        }
+        None => {
+            let mut contents = Vec::new();
+            file.read_to_end(&mut contents).map(|_| contents)
+        }
+    }
+    .map_err(|e| {
         format!(
             "failed to read file as text at path: {} with error: {e}",
             path.display()
@@ -367,6 +442,91 @@ pub async fn read_file<R: Runtime>(
     Ok(tauri::ipc::Response::new(contents))
 }
 
+/// The progress of a [`read_file`], sent after each chunk read.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadProgress {
+    /// Number of bytes read so far.
+    read: u64,
+    /// Length of the file.
+    total: u64,
+}
+
+/// Reads the whole file in chunks, calling `on_progress` with the number of bytes read so far
+/// and the length of the file after each chunk.
+fn read_with_progress(
+    file: &mut File,
+    mut on_progress: impl FnMut(u64, u64),
+) -> std::io::Result<Vec<u8>> {
+    const CHUNK_SIZE: usize = 64 * 1024;
+
+    let total = file.metadata()?.len();
+    let mut contents = Vec::with_capacity(total as usize);
+    let mut chunk = vec![0; CHUNK_SIZE];
+    loop {
+        let len = read_chunk(file, &mut chunk)?;
+        if len == 0 {
+            return Ok(contents);
+        }
+        contents.extend_from_slice(&chunk[..len]);
+        let read = contents.len() as u64;
+        // the file may have grown since it was stat'ed
+        on_progress(read, total.max(read));
+    }
+}
+
+#[tauri::command]
+pub async fn read_file_with_stat<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<BaseOptions>,
+) -> CommandResult<tauri::ipc::Response> {
+    let (mut file, path) = resolve_file(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        OpenOptions {
+            base: BaseOptions {
+                base_dir: options.as_ref().and_then(|o| o.base_dir),
+            },
+            options: crate::OpenOptions {
+                read: true,
+                ..Default::default()
+            },
+        },
+    )?;
+
+    let (mut contents, info) = read_with_stat(&mut file).map_err(|e| {
+        format!(
+            "failed to read file at path: {} with error: {e}",
+            path.display()
+        )
+    })?;
+
+    // This is an optimization to include the serialized file info and its length (as bigendian bytes)
+    // at the end of returned vector so we can use `tauri::ipc::Response`
+    // and avoid serialization overhead of the contents.
+    let info = serde_json::to_vec(&info)?;
+    let info_len = (info.len() as u64).to_be_bytes();
+    contents.extend(info);
+    contents.extend(info_len);
+
+    Ok(tauri::ipc::Response::new(contents))
+}
+
+/// Reads the whole file and stats the same handle.
+///
+/// The file is stat'ed before reading, so a concurrent write can only make
+/// the info look older than the contents, never newer.
+fn read_with_stat(file: &mut File) -> std::io::Result<(Vec<u8>, FileInfo)> {
+    let metadata = file.metadata()?;
+    let mut contents = Vec::with_capacity(metadata.len() as usize);
+    file.read_to_end(&mut contents)?;
+    Ok((contents, get_stat(metadata)))
+}
+
 // TODO, remove in v3, rely on `read_file` command instead
 #[tauri::command]
 pub async fn read_text_file<R: Runtime>(
@@ -376,7 +536,7 @@ pub async fn read_text_file<R: Runtime>(
     path: SafeFilePath,
     options: Option<BaseOptions>,
 ) -> CommandResult<tauri::ipc::Response> {
-    read_file(webview, global_scope, command_scope, path, options).await
+    read_file(webview, global_scope, command_scope, path, options, None).await
 }
 
 #[tauri::command]
@@ -545,6 +705,223 @@ pub fn rename<R: Runtime>(
         .map_err(Into::into)
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveIntoOptions {
+    paths_base_dir: Option<BaseDirectory>,
+    dest_dir_base_dir: Option<BaseDirectory>,
+    #[serde(default)]
+    overwrite: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveIntoResult {
+    source: PathBuf,
+    destination: Option<PathBuf>,
+    error: Option<String>,
+}
+
+#[tauri::command]
+pub fn move_into<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    paths: Vec<SafeFilePath>,
+    dest_dir: SafeFilePath,
+    options: Option<MoveIntoOptions>,
+) -> CommandResult<Vec<MoveIntoResult>> {
+    let resolved_dest_dir = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        dest_dir,
+        options.as_ref().and_then(|o| o.dest_dir_base_dir),
+    )?;
+    if !resolved_dest_dir.is_dir() {
+        return Err(format!("{} is not a directory", resolved_dest_dir.display()).into());
+    }
+    let overwrite = options.as_ref().is_some_and(|o| o.overwrite);
+
+    let results = paths
+        .into_iter()
+        .map(|path| {
+            let source = path.clone().into_path().unwrap_or_default();
+            let result = resolve_path(
+                &webview,
+                &global_scope,
+                &command_scope,
+                path,
+                options.as_ref().and_then(|o| o.paths_base_dir),
+            )
+            .and_then(|source| {
+                let destination = destination_in(&resolved_dest_dir, &source, overwrite)?;
+                let destination = resolve_path(
+                    &webview,
+                    &global_scope,
+                    &command_scope,
+                    SafeFilePath::try_from(destination)?,
+                    None,
+                )?;
+                move_path(&source, &destination, overwrite).map_err(|e| {
+                    format!(
+                        "failed to move {} to {} with error: {e}",
+                        source.display(),
+                        destination.display()
+                    )
+                })?;
+                Ok((source, destination))
+            });
+
+            match result {
+                Ok((source, destination)) => MoveIntoResult {
+                    source,
+                    destination: Some(destination),
+                    error: None,
+                },
+                Err(e) => MoveIntoResult {
+                    source,
+                    destination: None,
+                    error: Some(e.to_string()),
+                },
+            }
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// How many ` (n)` suffixes [`destination_in`] tries before giving up.
+const MAX_NAME_SUFFIX: usize = 1000;
+
+/// Returns the path `source` should be moved to inside `dest_dir`, keeping its file name.
+///
+/// Unless `overwrite` is set, a ` (n)` suffix is added to the file stem until the name is free.
+fn destination_in(dest_dir: &Path, source: &Path, overwrite: bool) -> std::io::Result<PathBuf> {
+    let file_name = source.file_name().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{} has no file name", source.display()),
+        )
+    })?;
+
+    let destination = dest_dir.join(file_name);
+    if overwrite || destination == source || !destination.exists() {
+        return Ok(destination);
+    }
+
+    let name = Path::new(file_name);
+    let stem = name.file_stem().unwrap_or(file_name).to_string_lossy();
+    let extension = name.extension().map(|e| e.to_string_lossy());
+    (1..=MAX_NAME_SUFFIX)
+        .map(|n| {
+            dest_dir.join(match &extension {
+                Some(extension) => format!("{stem} ({n}).{extension}"),
+                None => format!("{stem} ({n})"),
+            })
+        })
+        .find(|candidate| !candidate.exists())
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!(
+                    "no free file name for {} in {}",
+                    name.display(),
+                    dest_dir.display()
+                ),
+            )
+        })
+}
+
+/// Moves `from` to `to`, copying and removing the source when they are on different devices.
+///
+/// With `overwrite`, an existing `to` is only removed once `from` took its place,
+/// it is put back if the move fails.
+fn move_path(from: &Path, to: &Path, overwrite: bool) -> std::io::Result<()> {
+    if from == to {
+        return Ok(());
+    }
+    if from.starts_with(to) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{} is inside {}", from.display(), to.display()),
+        ));
+    }
+
+    let replaced = if overwrite && to.symlink_metadata().is_ok() {
+        let aside = replaced_path(to)?;
+        std::fs::rename(to, &aside)?;
+        Some(aside)
+    } else {
+        None
+    };
+
+    let moved = match std::fs::rename(from, to) {
+        Err(e) if is_cross_device(&e) => copy_recursive(from, to)
+            .inspect_err(|_| {
+                let _ = remove_path(to);
+            })
+            .and_then(|()| remove_path(from)),
+        result => result,
+    };
+
+    match replaced {
+        Some(replaced) if moved.is_ok() => remove_path(&replaced),
+        Some(replaced) => {
+            std::fs::rename(replaced, to)?;
+            moved
+        }
+        None => moved,
+    }
+}
+
+/// Returns a free sibling of `path` to move it aside to while it is being replaced.
+fn replaced_path(path: &Path) -> std::io::Result<PathBuf> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    (0..MAX_NAME_SUFFIX)
+        .map(|n| path.with_file_name(format!(".{file_name}.replaced-{n}")))
+        .find(|candidate| candidate.symlink_metadata().is_err())
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("no free file name to move {} aside", path.display()),
+            )
+        })
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() && !path.is_symlink() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+fn is_cross_device(error: &std::io::Error) -> bool {
+    // EXDEV on unix, ERROR_NOT_SAME_DEVICE on Windows
+    #[cfg(unix)]
+    const CROSS_DEVICE: i32 = 18;
+    #[cfg(windows)]
+    const CROSS_DEVICE: i32 = 17;
+    #[cfg(not(any(unix, windows)))]
+    const CROSS_DEVICE: i32 = -1;
+
+    error.raw_os_error() == Some(CROSS_DEVICE)
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> std::io::Result<()> {
+    if from.is_dir() && !from.is_symlink() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        std::fs::copy(from, to).map(|_| ())
+    }
+}
+
 #[derive(Serialize_repr, Deserialize_repr, Clone, Copy, Debug)]
 #[repr(u16)]
 pub enum SeekMode {
@@ -778,6 +1155,8 @@ pub struct WriteFileOptions {
     create_new: bool,
     #[allow(unused)]
     mode: Option<u32>,
+    #[serde(default)]
+    sync: bool,
 }
 
 fn default_create_value() -> bool {
@@ -816,6 +1195,7 @@ pub async fn write_file<R: Runtime>(
         .get("options")
         .and_then(|p| p.to_str().ok())
         .and_then(|opts| serde_json::from_str(opts).ok());
+    let sync = options.as_ref().is_some_and(|opts| opts.sync);
 
     let (mut file, path) = resolve_file(
         &webview,
@@ -853,7 +1233,7 @@ pub async fn write_file<R: Runtime>(
         },
     )?;
 
-    file.write_all(&data)
+    write_all(&mut file, &data, sync)
         .map_err(|e| {
             format!(
                 "failed to write bytes to file at path: {} with error: {e}",
@@ -863,6 +1243,17 @@ pub async fn write_file<R: Runtime>(
         .map_err(Into::into)
 }
 
+/// Writes `data` to `file`, flushing it to the storage device when `sync` is set.
+///
+/// Fails if `file` was not opened for writing.
+fn write_all(file: &mut File, data: &[u8], sync: bool) -> std::io::Result<()> {
+    file.write_all(data)?;
+    if sync {
+        file.sync_all()?;
+    }
+    Ok(())
+}
+
 // TODO, remove in v3, rely on `write_file` command instead
 #[tauri::command]
 pub async fn write_text_file<R: Runtime>(
@@ -892,14 +1283,121 @@ pub fn exists<R: Runtime>(
     Ok(resolved_path.exists())
 }
 
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessOptions {
+    base_dir: Option<BaseDirectory>,
+    #[serde(default)]
+    read: bool,
+    #[serde(default)]
+    write: bool,
+    #[serde(default)]
+    exec: bool,
+}
+
+/// What a path is and whether the current process can access it,
+/// checks that were not requested are reported as `false`.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessInfo {
+    exists: bool,
+    is_file: bool,
+    is_dir: bool,
+    readable: bool,
+    writable: bool,
+    executable: bool,
+}
+
 #[tauri::command]
-pub async fn size<R: Runtime>(
+pub fn access<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<AccessOptions>,
+) -> CommandResult<AccessInfo> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.base_dir,
+    )?;
+    Ok(check_access(&resolved_path, &options))
+}
+
+fn check_access(path: &Path, options: &AccessOptions) -> AccessInfo {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return AccessInfo::default();
+    };
+
+    AccessInfo {
+        exists: true,
+        is_file: metadata.is_file(),
+        is_dir: metadata.is_dir(),
+        readable: options.read && is_accessible(path, &metadata, Access::Read),
+        writable: options.write && is_accessible(path, &metadata, Access::Write),
+        executable: options.exec && is_accessible(path, &metadata, Access::Execute),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Access {
+    Read,
+    Write,
+    Execute,
+}
+
+#[cfg(unix)]
+fn is_accessible(path: &Path, _metadata: &std::fs::Metadata, access: Access) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(path) = std::ffi::CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let mode = match access {
+        Access::Read => libc::R_OK,
+        Access::Write => libc::W_OK,
+        Access::Execute => libc::X_OK,
+    };
+    // checked against the effective ids, which the file operations use, rather than the real ones
+    #[cfg(not(target_os = "android"))]
+    let flags = libc::AT_EACCESS;
+    // Android doesn't support `AT_EACCESS`, its apps don't run with different effective ids anyway
+    #[cfg(target_os = "android")]
+    let flags = 0;
+    // SAFETY: `path` is a valid nul-terminated string that outlives the call
+    unsafe { libc::faccessat(libc::AT_FDCWD, path.as_ptr(), mode, flags) == 0 }
+}
+
+#[cfg(not(unix))]
+fn is_accessible(path: &Path, metadata: &std::fs::Metadata, access: Access) -> bool {
+    match access {
+        Access::Read if metadata.is_dir() => std::fs::read_dir(path).is_ok(),
+        Access::Read => std::fs::File::open(path).is_ok(),
+        // the read-only attribute is ignored on directories
+        Access::Write => metadata.is_dir() || !metadata.permissions().readonly(),
+        // directories can always be traversed, files are executed based on their extension
+        Access::Execute => {
+            metadata.is_dir()
+                || path.extension().is_some_and(|ext| {
+                    ["exe", "com", "bat", "cmd"]
+                        .iter()
+                        .any(|executable| ext.eq_ignore_ascii_case(executable))
+                })
+        }
+    }
+}
+
+#[tauri::command]
+pub fn realpath<R: Runtime>(
     webview: Webview<R>,
     global_scope: GlobalScope<Entry>,
     command_scope: CommandScope<Entry>,
     path: SafeFilePath,
     options: Option<BaseOptions>,
-) -> CommandResult<u64> {
+) -> CommandResult<String> {
     let resolved_path = resolve_path(
         &webview,
         &global_scope,
@@ -907,13 +1405,257 @@ pub async fn size<R: Runtime>(
         path,
         options.as_ref().and_then(|o| o.base_dir),
     )?;
+    let canonical_path = canonicalize_in_scope(&resolved_path, |path| {
+        resolve_path(
+            &webview,
+            &global_scope,
+            &command_scope,
+            SafeFilePath::try_from(path)?,
+            None,
+        )
+    })?;
+    Ok(canonical_path.to_string_lossy().into_owned())
+}
+
+/// Canonicalizes `path` and checks the result against the scope with `resolve_path`,
+/// as symlinks and `..` components can point outside of the scope.
+fn canonicalize_in_scope(
+    path: &Path,
+    resolve_path: impl FnOnce(PathBuf) -> CommandResult<PathBuf>,
+) -> CommandResult<PathBuf> {
+    let canonical_path = dunce::canonicalize(path).map_err(|e| {
+        format!(
+            "failed to canonicalize path: {} with error: {e}",
+            path.display()
+        )
+    })?;
+    resolve_path(canonical_path)
+}
+
+#[tauri::command]
+pub fn get_xattr<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    name: String,
+    options: Option<BaseOptions>,
+) -> CommandResult<Option<Vec<u8>>> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+    crate::xattrs::get(&resolved_path, &name).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn set_xattr<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    name: String,
+    value: Vec<u8>,
+    options: Option<BaseOptions>,
+) -> CommandResult<()> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+    crate::xattrs::set(&resolved_path, &name, &value).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn list_xattr<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<BaseOptions>,
+) -> CommandResult<Vec<String>> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+    crate::xattrs::list(&resolved_path).map_err(Into::into)
+}
+
+/// Lists the items in the trash whose original path is in scope.
+// async to run off the main thread, where COM is initialized as single threaded on Windows
+#[tauri::command]
+pub async fn list_trash<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+) -> CommandResult<Vec<crate::trash::TrashItem>> {
+    Ok(crate::trash::list()?
+        .into_iter()
+        .filter(|item| resolve_trash_item(&webview, &global_scope, &command_scope, item).is_ok())
+        .collect())
+}
+
+/// Restores the item `id` of the trash, if its original path is in scope.
+#[tauri::command]
+pub async fn restore_from_trash<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    id: String,
+) -> CommandResult<()> {
+    let item = crate::trash::list()?
+        .into_iter()
+        .find(|item| item.id == id)
+        .ok_or_else(|| Error::TrashItemNotFound(id.clone()))?;
+    resolve_trash_item(&webview, &global_scope, &command_scope, &item)?;
+    crate::trash::restore(&id).map_err(Into::into)
+}
+
+fn resolve_trash_item<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+    item: &crate::trash::TrashItem,
+) -> CommandResult<PathBuf> {
+    resolve_path(
+        webview,
+        global_scope,
+        command_scope,
+        SafeFilePath::try_from(&item.original_path)?,
+        None,
+    )
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilesEqualOptions {
+    a_base_dir: Option<BaseDirectory>,
+    b_base_dir: Option<BaseDirectory>,
+}
+
+#[tauri::command]
+pub async fn files_equal<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    a: SafeFilePath,
+    b: SafeFilePath,
+    options: Option<FilesEqualOptions>,
+) -> CommandResult<bool> {
+    let resolved_a = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        a,
+        options.as_ref().and_then(|o| o.a_base_dir),
+    )?;
+    let resolved_b = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        b,
+        options.as_ref().and_then(|o| o.b_base_dir),
+    )?;
+    let equal = contents_equal(&resolved_a, &resolved_b).map_err(|e| {
+        format!(
+            "failed to compare files at path: {} and path: {} with error: {e}",
+            resolved_a.display(),
+            resolved_b.display()
+        )
+    })?;
+    Ok(equal)
+}
+
+/// Compares the contents of two files chunk by chunk, stopping at the first difference.
+fn contents_equal(a: &Path, b: &Path) -> std::io::Result<bool> {
+    const CHUNK_SIZE: usize = 64 * 1024;
+
+    let (mut a, mut b) = (File::open(a)?, File::open(b)?);
+    if a.metadata()?.len() != b.metadata()?.len() {
+        return Ok(false);
+    }
+
+    let (mut a_chunk, mut b_chunk) = (vec![0; CHUNK_SIZE], vec![0; CHUNK_SIZE]);
+    loop {
+        let a_len = read_chunk(&mut a, &mut a_chunk)?;
+        let b_len = read_chunk(&mut b, &mut b_chunk)?;
+        // the files may have been modified since their sizes were compared
+        if a_chunk[..a_len] != b_chunk[..b_len] {
+            return Ok(false);
+        }
+        if a_len == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+/// Fills `buf` from `file`, returning less than its length only at the end of the file.
+fn read_chunk(file: &mut File, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match file.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(len)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeOptions {
+    #[serde(flatten)]
+    base: BaseOptions,
+    /// Count the targets of the symlinks inside of the directory, defaults to `false`.
+    ///
+    /// Symlinks whose target is not allowed by the scope are not followed.
+    follow_symlinks: Option<bool>,
+}
+
+#[tauri::command]
+pub async fn size<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<SizeOptions>,
+) -> CommandResult<u64> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.as_ref().and_then(|o| o.base.base_dir),
+    )?;
+    let follow_symlinks = options
+        .as_ref()
+        .and_then(|o| o.follow_symlinks)
+        .unwrap_or(false);
+
+    let is_allowed =
+        |path: &Path| is_in_scope(&webview, &global_scope, &command_scope, path.to_path_buf());
 
     let metadata = resolved_path.metadata()?;
 
     if metadata.is_file() {
         Ok(metadata.len())
     } else {
-        let size = get_dir_size(&resolved_path).map_err(|e| {
+        let size = get_dir_size(
+            &resolved_path,
+            follow_symlinks.then_some(&is_allowed),
+            &mut HashSet::new(),
+        )
+        .map_err(|e| {
             format!(
                 "failed to get size at path: {} with error: {e}",
                 resolved_path.display()
@@ -924,23 +1666,60 @@ pub async fn size<R: Runtime>(
     }
 }
 
-fn get_dir_size(path: &PathBuf) -> CommandResult<u64> {
+/// Sums the sizes of the files in `path` and its subdirectories.
+///
+/// The symlinks are followed when their target is allowed by `follow_symlinks`, the directories
+/// already counted are then tracked in `visited`, as a symlink can lead back to one of them.
+fn get_dir_size<F: Fn(&Path) -> bool>(
+    path: &Path,
+    follow_symlinks: Option<&F>,
+    visited: &mut HashSet<PathBuf>,
+) -> CommandResult<u64> {
+    if follow_symlinks.is_some() && !visited.insert(dunce::canonicalize(path)?) {
+        return Ok(0);
+    }
+
     let mut size = 0;
 
     for entry in std::fs::read_dir(path)? {
         let entry = entry?;
-        let metadata = entry.metadata()?;
+        let mut path = entry.path();
+        let mut metadata = entry.metadata()?;
+        if let Some(is_allowed) = follow_symlinks.filter(|_| metadata.is_symlink()) {
+            // a broken symlink or one leading out of the scope counts as the link itself
+            if let Some(target) = allowed_symlink_target(&path, is_allowed) {
+                metadata = std::fs::metadata(&target)?;
+                path = target;
+            }
+        }
 
         if metadata.is_file() {
             size += metadata.len();
         } else if metadata.is_dir() {
-            size += get_dir_size(&entry.path())?;
+            size += get_dir_size(&path, follow_symlinks, visited)?;
         }
     }
 
     Ok(size)
 }
 
+/// Returns the canonical target of the symlink `path` if it is allowed by `is_allowed`.
+fn allowed_symlink_target(path: &Path, is_allowed: impl Fn(&Path) -> bool) -> Option<PathBuf> {
+    dunce::canonicalize(path)
+        .ok()
+        .filter(|target| is_allowed(target))
+}
+
+fn is_in_scope<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+    path: PathBuf,
+) -> bool {
+    SafeFilePath::try_from(path)
+        .is_ok_and(|path| resolve_path(webview, global_scope, command_scope, path, None).is_ok())
+}
+
 #[cfg(not(target_os = "android"))]
 pub fn resolve_file<R: Runtime>(
     webview: &Webview<R>,
@@ -1244,7 +2023,10 @@ fn get_stat(metadata: std::fs::Metadata) -> FileInfo {
 mod test {
     use std::io::{BufRead, BufReader};
 
-    use super::LinesBytes;
+    use super::{
+        check_access, contents_equal, destination_in, move_path, read_with_progress,
+        read_with_stat, write_all, AccessInfo, AccessOptions, LinesBytes, MAX_NAME_SUFFIX,
+    };
 
     #[test]
     fn safe_file_path_parse() {
@@ -1260,6 +2042,237 @@ mod test {
         ));
     }
 
+    #[test]
+    fn compares_file_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        // larger than a chunk, so the difference is only found after the first one
+        let contents = vec![7; 100 * 1024];
+        let mut changed = contents.clone();
+        *changed.last_mut().unwrap() = 8;
+
+        let write = |name: &str, contents: &[u8]| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            path
+        };
+        let original = write("original", &contents);
+        let copy = write("copy", &contents);
+        let changed = write("changed", &changed);
+        let truncated = write("truncated", &contents[1..]);
+
+        assert!(contents_equal(&original, &copy).unwrap());
+        assert!(!contents_equal(&original, &changed).unwrap());
+        assert!(!contents_equal(&original, &truncated).unwrap());
+        assert!(contents_equal(&original, &dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn read_with_stat_size_matches_contents() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file");
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(b"hello world")
+            .unwrap();
+
+        let (contents, info) = read_with_stat(&mut std::fs::File::open(&path).unwrap()).unwrap();
+
+        assert_eq!(contents, b"hello world");
+        assert_eq!(info.size, contents.len() as u64);
+        assert!(info.is_file);
+    }
+
+    #[test]
+    fn reports_read_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file");
+        // two full chunks and a partial one
+        let len = 2 * 64 * 1024 + 100;
+        let contents: Vec<u8> = (0..len).map(|i| i as u8).collect();
+        std::fs::write(&path, &contents).unwrap();
+
+        let mut progress = Vec::new();
+        let read = read_with_progress(&mut std::fs::File::open(&path).unwrap(), |read, total| {
+            progress.push((read, total))
+        })
+        .unwrap();
+
+        assert_eq!(read, contents);
+        let total = len as u64;
+        assert_eq!(
+            progress,
+            [(64 * 1024, total), (2 * 64 * 1024, total), (total, total)]
+        );
+    }
+
+    #[test]
+    fn write_all_with_sync() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file");
+
+        let mut file = std::fs::File::create(&path).unwrap();
+        write_all(&mut file, b"durable", true).unwrap();
+
+        // read back through a separate handle while the written one is still open
+        let mut contents = Vec::new();
+        std::io::Read::read_to_end(&mut std::fs::File::open(&path).unwrap(), &mut contents)
+            .unwrap();
+        assert_eq!(contents, b"durable");
+
+        let mut read_only = std::fs::File::open(&path).unwrap();
+        assert!(write_all(&mut read_only, b"lost", true).is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), b"durable");
+    }
+
+    fn all_checks() -> AccessOptions {
+        AccessOptions {
+            base_dir: None,
+            read: true,
+            write: true,
+            exec: true,
+        }
+    }
+
+    #[test]
+    fn access_on_a_writable_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file");
+        std::fs::write(&path, b"contents").unwrap();
+
+        let info = check_access(&path, &all_checks());
+        let only_exists = check_access(&path, &AccessOptions::default());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(info.exists && info.is_file && !info.is_dir);
+        assert!(info.readable && info.writable && !info.executable);
+        assert!(only_exists.exists && !only_exists.readable && !only_exists.writable);
+        assert_eq!(
+            check_access(&path, &all_checks()),
+            AccessInfo::default(),
+            "a missing path is reported as not existing"
+        );
+    }
+
+    #[test]
+    fn access_on_a_read_only_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file");
+        std::fs::write(&path, b"contents").unwrap();
+        let mut permissions = std::fs::metadata(&path).unwrap().permissions();
+        permissions.set_readonly(true);
+        std::fs::set_permissions(&path, permissions.clone()).unwrap();
+
+        let info = check_access(&path, &all_checks());
+        // so the temp dir can be removed on Windows
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        std::fs::set_permissions(&path, permissions).unwrap();
+
+        assert!(info.exists && info.is_file && info.readable);
+        // root can write to read-only files
+        #[cfg(unix)]
+        let privileged = unsafe { libc::geteuid() } == 0;
+        #[cfg(not(unix))]
+        let privileged = false;
+        assert_eq!(info.writable, privileged);
+    }
+
+    #[test]
+    fn move_into_suffixes_colliding_names() {
+        let root = tempfile::tempdir().unwrap();
+        let (src, dest) = (root.path().join("src"), root.path().join("dest"));
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::create_dir_all(&dest).unwrap();
+
+        for name in ["report.txt", "notes"] {
+            std::fs::write(src.join(name), name).unwrap();
+        }
+        std::fs::write(dest.join("report.txt"), "existing").unwrap();
+        std::fs::write(dest.join("report (1).txt"), "existing").unwrap();
+
+        let moved = ["report.txt", "notes"]
+            .iter()
+            .map(|name| {
+                let source = src.join(name);
+                let destination = destination_in(&dest, &source, false).unwrap();
+                move_path(&source, &destination, false).unwrap();
+                destination
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(moved, [dest.join("report (2).txt"), dest.join("notes")]);
+        assert_eq!(std::fs::read_to_string(&moved[0]).unwrap(), "report.txt");
+        assert_eq!(std::fs::read_to_string(&moved[1]).unwrap(), "notes");
+        assert_eq!(
+            std::fs::read_to_string(dest.join("report.txt")).unwrap(),
+            "existing"
+        );
+        assert!(std::fs::read_dir(&src).unwrap().next().is_none());
+    }
+
+    #[test]
+    fn move_into_overwrites_colliding_names() {
+        let root = tempfile::tempdir().unwrap();
+        let (src, dest) = (root.path().join("src"), root.path().join("dest"));
+        std::fs::create_dir_all(src.join("photos")).unwrap();
+        std::fs::create_dir_all(dest.join("photos")).unwrap();
+        std::fs::write(src.join("photos").join("new.png"), "new").unwrap();
+        std::fs::write(dest.join("photos").join("old.png"), "old").unwrap();
+        std::fs::write(src.join("a.txt"), "new").unwrap();
+        std::fs::write(dest.join("a.txt"), "old").unwrap();
+
+        for name in ["photos", "a.txt"] {
+            let source = src.join(name);
+            let destination = destination_in(&dest, &source, true).unwrap();
+            assert_eq!(destination, dest.join(name));
+            move_path(&source, &destination, true).unwrap();
+        }
+
+        assert_eq!(std::fs::read_to_string(dest.join("a.txt")).unwrap(), "new");
+        assert!(dest.join("photos").join("new.png").exists());
+        assert!(!dest.join("photos").join("old.png").exists());
+        assert!(!src.join("photos").exists());
+        // the replaced entries were cleaned up
+        assert_eq!(std::fs::read_dir(&dest).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn move_into_keeps_the_destination_when_the_move_fails() {
+        let root = tempfile::tempdir().unwrap();
+        let dest = root.path().join("photos");
+        std::fs::create_dir_all(dest.join("inner")).unwrap();
+        std::fs::write(dest.join("old.png"), "old").unwrap();
+
+        // the source is gone
+        assert!(move_path(&root.path().join("missing"), &dest, true).is_err());
+        assert_eq!(
+            std::fs::read_to_string(dest.join("old.png")).unwrap(),
+            "old"
+        );
+
+        // the source would be removed along with the destination
+        let err = move_path(&dest.join("inner"), &dest, true).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(dest.join("inner").is_dir());
+
+        assert_eq!(std::fs::read_dir(root.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn move_into_gives_up_on_colliding_names() {
+        let root = tempfile::tempdir().unwrap();
+        let source = root.path().join("src").join("a.txt");
+        std::fs::write(root.path().join("a.txt"), "").unwrap();
+        for n in 1..=MAX_NAME_SUFFIX {
+            std::fs::write(root.path().join(format!("a ({n}).txt")), "").unwrap();
+        }
+
+        let err = destination_in(root.path(), &source, false).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    }
+
     #[test]
     fn test_lines_bytes() {
         let base = String::from("line 1\nline2\nline 3\nline 4");
@@ -1279,4 +2292,95 @@ mod test {
         assert_eq!(string1, string3);
         assert_eq!(string2, string3);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn realpath_rejects_symlinks_escaping_the_scope() {
+        use super::{canonicalize_in_scope, CommandError};
+        use crate::Error;
+
+        let root = tempfile::tempdir().unwrap();
+        let scope = root.path().join("scope");
+        let outside = root.path().join("outside");
+        std::fs::create_dir_all(&scope).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(scope.join("file.txt"), "inside").unwrap();
+        std::fs::write(outside.join("secret.txt"), "outside").unwrap();
+        std::os::unix::fs::symlink(scope.join("file.txt"), scope.join("inside-link")).unwrap();
+        std::os::unix::fs::symlink(outside.join("secret.txt"), scope.join("outside-link")).unwrap();
+
+        let scope = std::fs::canonicalize(&scope).unwrap();
+        let in_scope = |path: std::path::PathBuf| {
+            if path.starts_with(&scope) {
+                Ok(path)
+            } else {
+                Err(CommandError::Plugin(Error::PathForbidden(path)))
+            }
+        };
+
+        let resolved = canonicalize_in_scope(&scope.join("inside-link"), in_scope).unwrap();
+        assert_eq!(resolved, scope.join("file.txt"));
+
+        let resolved = canonicalize_in_scope(&scope.join("../scope/./file.txt"), in_scope).unwrap();
+        assert_eq!(resolved, scope.join("file.txt"));
+
+        let res = canonicalize_in_scope(&scope.join("outside-link"), in_scope);
+        assert!(matches!(
+            res,
+            Err(CommandError::Plugin(Error::PathForbidden(path))) if path.ends_with("outside/secret.txt")
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copies_symlinks_with_and_without_following() {
+        use super::copy_path;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        std::fs::write(dir.join("file.txt"), "contents").unwrap();
+        std::os::unix::fs::symlink("file.txt", dir.join("link")).unwrap();
+
+        copy_path(&dir.join("link"), &dir.join("followed"), true).unwrap();
+        assert!(!dir.join("followed").is_symlink());
+        assert_eq!(std::fs::read(dir.join("followed")).unwrap(), b"contents");
+
+        // replaces the existing copy with the symlink itself
+        copy_path(&dir.join("link"), &dir.join("followed"), false).unwrap();
+        assert_eq!(
+            std::fs::read_link(dir.join("followed")).unwrap(),
+            std::path::Path::new("file.txt")
+        );
+        assert_eq!(std::fs::read(dir.join("followed")).unwrap(), b"contents");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dir_size_follows_symlinks_once() {
+        use super::get_dir_size;
+        use std::{collections::HashSet, path::Path};
+
+        let root = tempfile::tempdir().unwrap();
+        let dir = dunce::canonicalize(root.path()).unwrap().join("scope");
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("sub/file.txt"), [0; 10]).unwrap();
+        // a link back to the parent directory
+        std::os::unix::fs::symlink("..", dir.join("sub/parent")).unwrap();
+        std::os::unix::fs::symlink("sub/file.txt", dir.join("file-link")).unwrap();
+
+        let in_scope = |path: &Path| path.starts_with(&dir);
+        let follow = Some(&in_scope);
+        assert_eq!(
+            get_dir_size(&dir, None::<&fn(&Path) -> bool>, &mut HashSet::new()).unwrap(),
+            10
+        );
+        assert_eq!(get_dir_size(&dir, follow, &mut HashSet::new()).unwrap(), 20);
+
+        // the targets out of the scope are not followed
+        std::fs::write(root.path().join("outside.bin"), [0; 100]).unwrap();
+        std::os::unix::fs::symlink(root.path(), dir.join("root-link")).unwrap();
+        std::os::unix::fs::symlink(root.path().join("outside.bin"), dir.join("outside-link"))
+            .unwrap();
+        assert_eq!(get_dir_size(&dir, follow, &mut HashSet::new()).unwrap(), 20);
+    }
 }
@@ -31,6 +31,18 @@ pub enum Error {
     InvalidPathUrl,
     #[error("Unsafe PathBuf: {0}")]
     UnsafePathBuf(&'static str),
+    /// Extended attributes are not supported by the platform or the file system.
+    #[error("extended attributes are not supported on this platform or file system")]
+    XattrUnsupported,
+    #[error("invalid extended attribute name: {0}")]
+    InvalidXattrName(String),
+    /// The trash can't be listed or restored from on this platform, or without the `trash` feature.
+    #[error("listing and restoring the trash is not supported on this platform or without the `trash` feature")]
+    TrashUnsupported,
+    #[error("trash item not found: {0}")]
+    TrashItemNotFound(String),
+    #[error("{0}")]
+    Trash(String),
 }
 
 impl Serialize for Error {
@@ -9,7 +9,7 @@
     html_favicon_url = "https://github.com/tauri-apps/tauri/raw/dev/app-icon.png"
 )]
 
-use std::io::Read;
+use std::io::{Read, Write};
 
 use serde::Deserialize;
 use tauri::{
@@ -30,8 +30,10 @@ mod mobile;
 #[cfg(target_os = "android")]
 mod models;
 mod scope;
+mod trash;
 #[cfg(feature = "watch")]
 mod watcher;
+mod xattrs;
 
 #[cfg(not(target_os = "android"))]
 pub use desktop::Fs;
@@ -340,6 +342,34 @@ impl<R: Runtime> Fs<R> {
         .read_to_end(&mut buf)?;
         Ok(buf)
     }
+
+    /// Appends `contents` to the end of the file at `path`, creating it if it doesn't exist.
+    ///
+    /// The file is opened in append mode, so each write lands at its current end, but the contents
+    /// of concurrent writers may still interleave, see [`std::fs::OpenOptions::append`].
+    pub fn append<P: Into<FilePath>, C: AsRef<[u8]>>(
+        &self,
+        path: P,
+        contents: C,
+    ) -> std::io::Result<()> {
+        self.open(
+            path,
+            OpenOptions {
+                // `wa` on Android, `a` alone is not a valid mode there
+                write: true,
+                append: true,
+                create: true,
+                ..Default::default()
+            },
+        )?
+        .write_all(contents.as_ref())
+    }
+
+    /// Appends `line` and a line feed to the end of the file at `path`, creating it if it doesn't exist.
+    pub fn append_line<P: Into<FilePath>>(&self, path: P, line: &str) -> std::io::Result<()> {
+        // a single buffer, so the line feed isn't written separately from its line
+        self.append(path, format!("{line}\n"))
+    }
 }
 
 // implement ScopeObject here instead of in the scope module because it is also used on the build script
@@ -401,11 +431,13 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
             commands::read_dir,
             commands::read,
             commands::read_file,
+            commands::read_file_with_stat,
             commands::read_text_file,
             commands::read_text_file_lines,
             commands::read_text_file_lines_next,
             commands::remove,
             commands::rename,
+            commands::move_into,
             commands::seek,
             commands::stat,
             commands::lstat,
@@ -416,6 +448,14 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
             commands::write_file,
             commands::write_text_file,
             commands::exists,
+            commands::access,
+            commands::realpath,
+            commands::get_xattr,
+            commands::set_xattr,
+            commands::list_xattr,
+            commands::list_trash,
+            commands::restore_from_trash,
+            commands::files_equal,
             commands::size,
             #[cfg(feature = "watch")]
             watcher::watch,
@@ -459,3 +499,26 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
         })
         .build()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_to_files() {
+        let app = tauri::test::mock_app();
+        let fs = Fs(app.handle().clone());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+
+        fs.append_line(&path, "first").unwrap();
+        fs.append(&path, b"second ").unwrap();
+        fs.append_line(&path, "line").unwrap();
+        fs.append_line(&path, "third").unwrap();
+
+        assert_eq!(
+            fs.read_to_string(&path).unwrap(),
+            "first\nsecond line\nthird\n"
+        );
+    }
+}
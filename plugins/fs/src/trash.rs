@@ -0,0 +1,145 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Listing and restoring the items in the trash with the `trash` feature, backed by the `trash` crate
+//! on Windows and the platforms following the freedesktop.org trash specification.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::Result;
+
+pub use imp::{list, restore};
+
+/// An item in the trash.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashItem {
+    /// Platform specific identifier of the item, to restore it with.
+    pub id: String,
+    /// Path of the item before it was moved to the trash.
+    pub original_path: PathBuf,
+    /// When the item was moved to the trash, in seconds since the Unix epoch.
+    pub deleted_at: i64,
+}
+
+#[cfg(all(
+    feature = "trash",
+    any(
+        windows,
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd"
+    )
+))]
+mod imp {
+    use ::trash::os_limited;
+
+    use super::*;
+    use crate::Error;
+
+    /// Lists the items in the trash.
+    pub fn list() -> Result<Vec<TrashItem>> {
+        Ok(os_limited::list()
+            .map_err(map_err)?
+            .into_iter()
+            .map(|item| TrashItem {
+                id: item.id.to_string_lossy().into_owned(),
+                original_path: item.original_path(),
+                deleted_at: item.time_deleted,
+            })
+            .collect())
+    }
+
+    /// Moves the item `id` back to its original path, which must not exist.
+    pub fn restore(id: &str) -> Result<()> {
+        let item = os_limited::list()
+            .map_err(map_err)?
+            .into_iter()
+            .find(|item| item.id.to_string_lossy() == id)
+            .ok_or_else(|| Error::TrashItemNotFound(id.to_string()))?;
+        os_limited::restore_all([item]).map_err(map_err)
+    }
+
+    fn map_err(err: ::trash::Error) -> Error {
+        match err {
+            ::trash::Error::RestoreCollision { path, .. } => std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("{} already exists", path.display()),
+            )
+            .into(),
+            #[cfg(not(windows))]
+            ::trash::Error::FileSystem { source, .. } => source.into(),
+            err => Error::Trash(err.to_string()),
+        }
+    }
+}
+
+#[cfg(not(all(
+    feature = "trash",
+    any(
+        windows,
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd"
+    )
+)))]
+mod imp {
+    use super::*;
+    use crate::Error;
+
+    pub fn list() -> Result<Vec<TrashItem>> {
+        Err(Error::TrashUnsupported)
+    }
+
+    pub fn restore(_id: &str) -> Result<()> {
+        Err(Error::TrashUnsupported)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[ignore = "moves a file to the trash of the current user"]
+    #[cfg(all(
+        feature = "trash",
+        any(
+            windows,
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "openbsd",
+            target_os = "netbsd"
+        )
+    ))]
+    fn restores_deleted_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        // the trash reports the canonical original path
+        let dir = dunce::canonicalize(temp_dir.path()).unwrap();
+        let path = dir.join("deleted.txt");
+        std::fs::write(&path, "restore me").unwrap();
+
+        ::trash::delete(&path).unwrap();
+        assert!(!path.exists());
+
+        let item = super::list()
+            .unwrap()
+            .into_iter()
+            .find(|item| item.original_path == path)
+            .expect("the deleted file is listed");
+        super::restore(&item.id).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "restore me");
+
+        // it left the trash
+        assert!(matches!(
+            super::restore(&item.id),
+            Err(crate::Error::TrashItemNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,230 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Extended attributes, backed by xattrs on Unix and alternate data streams on Windows.
+
+use std::path::Path;
+
+use crate::{Error, Result};
+
+pub use imp::{get, list, set};
+
+#[cfg(unix)]
+mod imp {
+    use super::*;
+
+    /// Returns the value of the extended attribute `name`, or `None` if it is not set.
+    pub fn get(path: &Path, name: &str) -> Result<Option<Vec<u8>>> {
+        xattr::get(path, name).map_err(map_err)
+    }
+
+    /// Sets the extended attribute `name`, replacing its value if it is already set.
+    pub fn set(path: &Path, name: &str, value: &[u8]) -> Result<()> {
+        xattr::set(path, name, value).map_err(map_err)
+    }
+
+    /// Lists the names of the extended attributes set on `path`.
+    pub fn list(path: &Path) -> Result<Vec<String>> {
+        Ok(xattr::list(path)
+            .map_err(map_err)?
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect())
+    }
+
+    fn map_err(err: std::io::Error) -> Error {
+        let unsupported = err.kind() == std::io::ErrorKind::Unsupported
+            || matches!(err.raw_os_error(), Some(code) if code == libc::ENOTSUP || code == libc::EOPNOTSUPP);
+        if unsupported {
+            Error::XattrUnsupported
+        } else {
+            err.into()
+        }
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::{
+        ffi::OsString,
+        os::windows::ffi::{OsStrExt, OsStringExt},
+        path::PathBuf,
+    };
+
+    use windows_sys::Win32::{
+        Foundation::{
+            ERROR_HANDLE_EOF, ERROR_INVALID_FUNCTION, ERROR_INVALID_NAME, ERROR_NOT_SUPPORTED,
+            INVALID_HANDLE_VALUE,
+        },
+        Storage::FileSystem::{
+            FindClose, FindFirstStreamW, FindNextStreamW, FindStreamInfoStandard,
+            WIN32_FIND_STREAM_DATA,
+        },
+    };
+
+    use super::*;
+
+    /// Returns the content of the alternate data stream `name`, or `None` if it doesn't exist.
+    pub fn get(path: &Path, name: &str) -> Result<Option<Vec<u8>>> {
+        // the stream path is not found either when the file doesn't exist
+        path.metadata()?;
+        match std::fs::read(stream_path(path, name)?) {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(map_err(err)),
+        }
+    }
+
+    /// Writes the alternate data stream `name`, replacing its content if it already exists.
+    pub fn set(path: &Path, name: &str, value: &[u8]) -> Result<()> {
+        path.metadata()?;
+        std::fs::write(stream_path(path, name)?, value).map_err(map_err)
+    }
+
+    /// Lists the names of the alternate data streams of `path`, the unnamed main stream excluded.
+    pub fn list(path: &Path) -> Result<Vec<String>> {
+        let path: Vec<u16> = path.as_os_str().encode_wide().chain([0]).collect();
+        let mut data = WIN32_FIND_STREAM_DATA::default();
+        // SAFETY: `path` is nul-terminated and `data` is a `WIN32_FIND_STREAM_DATA` as required by `FindStreamInfoStandard`
+        let handle = unsafe {
+            FindFirstStreamW(
+                path.as_ptr(),
+                FindStreamInfoStandard,
+                &mut data as *mut _ as *mut _,
+                0,
+            )
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            let err = std::io::Error::last_os_error();
+            // directories without named streams have no stream at all
+            return if err.raw_os_error() == Some(ERROR_HANDLE_EOF as i32) {
+                Ok(Vec::new())
+            } else {
+                Err(map_err(err))
+            };
+        }
+
+        let mut names = Vec::new();
+        loop {
+            if let Some(name) = stream_name(&data) {
+                names.push(name);
+            }
+            // SAFETY: `handle` is a valid find handle, closed below
+            if unsafe { FindNextStreamW(handle, &mut data as *mut _ as *mut _) } == 0 {
+                break;
+            }
+        }
+        let err = std::io::Error::last_os_error();
+        // SAFETY: `handle` was returned by `FindFirstStreamW` and is not used afterwards
+        unsafe { FindClose(handle) };
+
+        if err.raw_os_error() == Some(ERROR_HANDLE_EOF as i32) {
+            Ok(names)
+        } else {
+            Err(map_err(err))
+        }
+    }
+
+    /// Extracts `name` from a stream found as `:name:$DATA`, `None` for the main stream `::$DATA`.
+    fn stream_name(data: &WIN32_FIND_STREAM_DATA) -> Option<String> {
+        let len = data
+            .cStreamName
+            .iter()
+            .position(|c| *c == 0)
+            .unwrap_or(data.cStreamName.len());
+        let name = OsString::from_wide(&data.cStreamName[..len]);
+        let name = name.to_string_lossy();
+        let name = name.strip_prefix(':')?.strip_suffix(":$DATA")?;
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    fn stream_path(path: &Path, name: &str) -> Result<PathBuf> {
+        if name.is_empty() || name.contains([':', '\\', '/', '\0']) {
+            return Err(Error::InvalidXattrName(name.into()));
+        }
+        let mut stream_path = path.as_os_str().to_owned();
+        stream_path.push(":");
+        stream_path.push(name);
+        Ok(stream_path.into())
+    }
+
+    fn map_err(err: std::io::Error) -> Error {
+        // stream names are validated beforehand, so an invalid name means the file system has no streams
+        let unsupported = [
+            ERROR_INVALID_FUNCTION,
+            ERROR_INVALID_NAME,
+            ERROR_NOT_SUPPORTED,
+        ]
+        .iter()
+        .any(|code| err.raw_os_error() == Some(*code as i32));
+        if unsupported {
+            Error::XattrUnsupported
+        } else {
+            err.into()
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    use super::*;
+
+    pub fn get(_path: &Path, _name: &str) -> Result<Option<Vec<u8>>> {
+        Err(Error::XattrUnsupported)
+    }
+
+    pub fn set(_path: &Path, _name: &str, _value: &[u8]) -> Result<()> {
+        Err(Error::XattrUnsupported)
+    }
+
+    pub fn list(_path: &Path) -> Result<Vec<String>> {
+        Err(Error::XattrUnsupported)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// Creates a file to test with, or `None` if the temp directory doesn't support extended attributes.
+    fn test_file() -> Option<(tempfile::TempDir, std::path::PathBuf)> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file");
+        std::fs::write(&path, "contents").unwrap();
+        match set(&path, "user.tauri.probe", b"") {
+            Err(Error::XattrUnsupported) => None,
+            res => {
+                res.unwrap();
+                Some((dir, path))
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_an_attribute() {
+        let Some((_dir, path)) = test_file() else {
+            return;
+        };
+
+        assert_eq!(get(&path, "user.tauri.tag").unwrap(), None);
+        set(&path, "user.tauri.tag", b"important").unwrap();
+        assert_eq!(
+            get(&path, "user.tauri.tag").unwrap().as_deref(),
+            Some(&b"important"[..])
+        );
+        set(&path, "user.tauri.tag", b"done").unwrap();
+        assert_eq!(
+            get(&path, "user.tauri.tag").unwrap().as_deref(),
+            Some(&b"done"[..])
+        );
+        assert!(list(&path).unwrap().contains(&"user.tauri.tag".to_string()));
+    }
+
+    #[test]
+    fn missing_file_is_an_io_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing");
+        assert!(matches!(get(&path, "user.tauri.tag"), Err(Error::Io(_))));
+        assert!(matches!(list(&path), Err(Error::Io(_))));
+    }
+}
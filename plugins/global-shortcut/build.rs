@@ -2,7 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-const COMMANDS: &[&str] = &["register", "unregister", "unregister_all", "is_registered"];
+const COMMANDS: &[&str] = &[
+    "register",
+    "unregister",
+    "unregister_all",
+    "reregister_all",
+    "is_registered",
+];
 
 fn main() {
     tauri_plugin::Builder::new(COMMANDS)
@@ -14,6 +14,19 @@ export interface ShortcutEvent {
   shortcut: string
   id: number
   state: 'Released' | 'Pressed'
+  /**
+   * Modifiers held when the event was triggered, which can include more than the registered ones.
+   * Falls back to the registered modifiers where the keyboard state can't be read, e.g. on Wayland.
+   *
+   * @since 2.4.0
+   */
+  modifiers: Array<'Shift' | 'Control' | 'Alt' | 'Super'>
+  /**
+   * Key of the triggering shortcut, as a [`KeyboardEvent.code`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/code) value, e.g. `KeyA`.
+   *
+   * @since 2.4.0
+   */
+  code: string
 }
 
 export type ShortcutHandler = (event: ShortcutEvent) => void
@@ -99,6 +112,23 @@ async function unregisterAll(): Promise<void> {
   return await invoke('plugin:global-shortcut|unregister_all', {})
 }
 
+/**
+ * Unregisters and registers again all the global shortcuts, keeping their handlers.
+ *
+ * Some systems silently drop the global shortcuts, e.g. on fast user switching,
+ * call this to make them work again without restarting the app.
+ *
+ * @example
+ * ```typescript
+ * import { reregisterAll } from '@tauri-apps/plugin-global-shortcut';
+ * await reregisterAll();
+ * ```
+ * @since 2.4.0
+ */
+async function reregisterAll(): Promise<void> {
+  return await invoke('plugin:global-shortcut|reregister_all', {})
+}
+
 /**
  * Determines whether the given shortcut is registered by this application or not.
  *
@@ -120,4 +150,4 @@ async function isRegistered(shortcut: string): Promise<boolean> {
   })
 }
 
-export { register, unregister, unregisterAll, isRegistered }
+export { register, unregister, unregisterAll, reregisterAll, isRegistered }
@@ -16,6 +16,7 @@ use std::{
     collections::HashMap,
     str::FromStr,
     sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use global_hotkey::GlobalHotKeyEvent;
@@ -31,8 +32,10 @@ use tauri::{
 };
 
 mod error;
+mod modifiers;
 
 pub use error::Error;
+pub use modifiers::current_modifiers;
 type Result<T> = std::result::Result<T, Error>;
 
 type HotKeyId = u32;
@@ -49,13 +52,91 @@ impl From<Shortcut> for ShortcutWrapper {
 impl TryFrom<&str> for ShortcutWrapper {
     type Error = global_hotkey::hotkey::HotKeyParseError;
     fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
-        Shortcut::from_str(value).map(ShortcutWrapper)
+        Shortcut::from_str(&normalize_shortcut(value)).map(ShortcutWrapper)
     }
 }
 
 struct RegisteredShortcut<R: Runtime> {
     shortcut: Shortcut,
     handler: Option<Arc<HandlerFn<R>>>,
+    debounce: Option<Debounce>,
+    scope: ShortcutScope,
+}
+
+/// When the handlers of a shortcut registered with [`GlobalShortcut::on_shortcut_scoped`] are called.
+///
+/// The shortcut is still registered globally, the events received out of its scope are dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ShortcutScope {
+    /// Whether the app is focused or not, like the shortcuts registered with [`GlobalShortcut::on_shortcut`].
+    #[default]
+    Global,
+    /// While one of the app windows is focused.
+    AppFocused,
+    /// While the window with this label is focused.
+    Window(String),
+}
+
+impl ShortcutScope {
+    /// Whether the scope includes the current state of the app,
+    /// `focused` returning the label of its focused window, if any.
+    fn contains(&self, focused: impl FnOnce() -> Option<String>) -> bool {
+        match self {
+            Self::Global => true,
+            Self::AppFocused => focused().is_some(),
+            Self::Window(label) => focused().as_ref() == Some(label),
+        }
+    }
+}
+
+/// Whether an event in `state` received at `now` should reach the handlers of a shortcut.
+///
+/// The scope is checked first, so the events it drops don't count for the debounce.
+fn should_dispatch(
+    scope: &ShortcutScope,
+    debounce: Option<&Debounce>,
+    state: ShortcutState,
+    now: Instant,
+    focused: impl FnOnce() -> Option<String>,
+) -> bool {
+    scope.contains(focused) && debounce.map_or(true, |debounce| debounce.should_fire(state, now))
+}
+
+/// Ignores the repeated [`ShortcutState::Pressed`] events sent while a shortcut is held.
+struct Debounce {
+    window: Duration,
+    last_pressed: Mutex<Option<Instant>>,
+}
+
+impl Debounce {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_pressed: Mutex::new(None),
+        }
+    }
+
+    /// Whether an event in `state` received at `now` should reach the handlers.
+    ///
+    /// A `Pressed` event is dropped if another one was let through less than `window` ago
+    /// and the shortcut was not released since.
+    fn should_fire(&self, state: ShortcutState, now: Instant) -> bool {
+        let mut last_pressed = self.last_pressed.lock().unwrap();
+        match state {
+            ShortcutState::Released => {
+                *last_pressed = None;
+                true
+            }
+            ShortcutState::Pressed => {
+                if last_pressed.is_some_and(|at| now.duration_since(at) < self.window) {
+                    false
+                } else {
+                    *last_pressed = Some(now);
+                    true
+                }
+            }
+        }
+    }
 }
 
 struct GlobalHotKeyManager(global_hotkey::GlobalHotKeyManager);
@@ -65,11 +146,21 @@ unsafe impl Send for GlobalHotKeyManager {}
 /// SAFETY: we ensure it is run on main thread only
 unsafe impl Sync for GlobalHotKeyManager {}
 
+/// A shortcut that failed to register when setting up the plugin in [lenient](Builder::lenient) mode.
+#[derive(Debug)]
+pub struct FailedRegistration {
+    /// The shortcut that could not be registered.
+    pub shortcut: Shortcut,
+    /// The registration error.
+    pub error: Error,
+}
+
 pub struct GlobalShortcut<R: Runtime> {
     #[allow(dead_code)]
     app: AppHandle<R>,
     manager: Arc<GlobalHotKeyManager>,
     shortcuts: Arc<Mutex<HashMap<HotKeyId, RegisteredShortcut<R>>>>,
+    failed_registrations: Vec<FailedRegistration>,
 }
 
 macro_rules! run_main_thread {
@@ -90,14 +181,21 @@ impl<R: Runtime> GlobalShortcut<R> {
         &self,
         shortcut: Shortcut,
         handler: Option<F>,
+        debounce: Option<Duration>,
+        scope: ShortcutScope,
     ) -> Result<()> {
         let id = shortcut.id();
         let handler = handler.map(|h| Arc::new(Box::new(h) as HandlerFn<R>));
         run_main_thread!(self.app, self.manager, |m| m.0.register(shortcut))?;
-        self.shortcuts
-            .lock()
-            .unwrap()
-            .insert(id, RegisteredShortcut { shortcut, handler });
+        self.shortcuts.lock().unwrap().insert(
+            id,
+            RegisteredShortcut {
+                shortcut,
+                handler,
+                debounce: debounce.map(Debounce::new),
+                scope,
+            },
+        );
         Ok(())
     }
 
@@ -118,6 +216,8 @@ impl<R: Runtime> GlobalShortcut<R> {
                 RegisteredShortcut {
                     shortcut,
                     handler: handler.clone(),
+                    debounce: None,
+                    scope: ShortcutScope::Global,
                 },
             );
         }
@@ -136,17 +236,69 @@ impl<R: Runtime> GlobalShortcut<R> {
         self.register_internal(
             try_into_shortcut(shortcut)?,
             None::<fn(&AppHandle<R>, &Shortcut, ShortcutEvent)>,
+            None,
+            ShortcutScope::Global,
         )
     }
 
     /// Register a shortcut with a handler.
+    ///
+    /// The handler receives the triggering shortcut, whose [`mods`](Shortcut::mods) and [`key`](Shortcut::key)
+    /// tell apart the shortcuts sharing a handler. Use [`current_modifiers`] to read the modifiers actually held,
+    /// which can include more than the registered ones.
     pub fn on_shortcut<S, F>(&self, shortcut: S, handler: F) -> Result<()>
     where
         S: TryInto<ShortcutWrapper>,
         S::Error: std::error::Error,
         F: Fn(&AppHandle<R>, &Shortcut, ShortcutEvent) + Send + Sync + 'static,
     {
-        self.register_internal(try_into_shortcut(shortcut)?, Some(handler))
+        self.register_internal(
+            try_into_shortcut(shortcut)?,
+            Some(handler),
+            None,
+            ShortcutScope::Global,
+        )
+    }
+
+    /// Register a shortcut with a handler that is only called while the shortcut is in `scope`,
+    /// e.g. while the app is focused, behaving like a local accelerator.
+    ///
+    /// The shortcut is still registered globally, so other apps can't use it while it's registered.
+    pub fn on_shortcut_scoped<S, F>(
+        &self,
+        shortcut: S,
+        scope: ShortcutScope,
+        handler: F,
+    ) -> Result<()>
+    where
+        S: TryInto<ShortcutWrapper>,
+        S::Error: std::error::Error,
+        F: Fn(&AppHandle<R>, &Shortcut, ShortcutEvent) + Send + Sync + 'static,
+    {
+        self.register_internal(try_into_shortcut(shortcut)?, Some(handler), None, scope)
+    }
+
+    /// Register a shortcut with a handler, ignoring the repeated [`ShortcutState::Pressed`] events
+    /// received within `debounce` of the last handled one, until the shortcut is released.
+    ///
+    /// Useful as some keyboards and platforms keep sending `Pressed` events while a shortcut is held.
+    pub fn on_shortcut_with_debounce<S, F>(
+        &self,
+        shortcut: S,
+        debounce: Duration,
+        handler: F,
+    ) -> Result<()>
+    where
+        S: TryInto<ShortcutWrapper>,
+        S::Error: std::error::Error,
+        F: Fn(&AppHandle<R>, &Shortcut, ShortcutEvent) + Send + Sync + 'static,
+    {
+        self.register_internal(
+            try_into_shortcut(shortcut)?,
+            Some(handler),
+            Some(debounce),
+            ShortcutScope::Global,
+        )
     }
 
     /// Register multiple shortcuts.
@@ -226,6 +378,36 @@ impl<R: Runtime> GlobalShortcut<R> {
         res.map_err(Into::into)
     }
 
+    /// Unregisters and registers again all the registered shortcuts, keeping their handlers.
+    ///
+    /// Some systems silently drop the global shortcuts, e.g. on fast user switching or when the display manager restarts,
+    /// call this when the app is notified of such a change to make them work again without restarting it.
+    /// Every shortcut is registered again even if one fails, the first error is returned.
+    ///
+    /// The plugin doesn't watch for these changes itself, as the systems don't report them consistently,
+    /// so it's up to the app to call this e.g. when its window is focused again or the session is unlocked.
+    pub fn reregister_all(&self) -> Result<()> {
+        let hotkeys = self
+            .shortcuts
+            .lock()
+            .unwrap()
+            .values()
+            .map(|s| s.shortcut)
+            .collect::<Vec<_>>();
+        run_main_thread!(self.app, self.manager, |m| {
+            reregister_shortcuts(
+                hotkeys,
+                |shortcut| m.0.unregister(shortcut).map_err(Into::into),
+                |shortcut| m.0.register(shortcut).map_err(Into::into),
+            )
+        })
+    }
+
+    /// The shortcuts given to the [`Builder`] that failed to register in [lenient](Builder::lenient) mode.
+    pub fn failed_registrations(&self) -> &[FailedRegistration] {
+        &self.failed_registrations
+    }
+
     /// Determines whether the given shortcut is registered by this application or not.
     ///
     /// If the shortcut is registered by another application, it will still return `false`.
@@ -252,7 +434,112 @@ impl<R: Runtime, T: Manager<R>> GlobalShortcutExt<R> for T {
 }
 
 fn parse_shortcut<S: AsRef<str>>(shortcut: S) -> Result<Shortcut> {
-    shortcut.as_ref().parse().map_err(Into::into)
+    normalize_shortcut(shortcut.as_ref())
+        .parse()
+        .map_err(Into::into)
+}
+
+/// Replaces the modifier aliases `global_hotkey` does not recognize with their canonical name.
+fn normalize_shortcut(shortcut: &str) -> String {
+    shortcut
+        .split('+')
+        .map(|token| match token.trim().to_uppercase().as_str() {
+            "WIN" | "WINDOWS" | "META" | "⌘" => "Super",
+            "OPT" | "⌥" => "Alt",
+            "⌃" => "Control",
+            "⇧" => "Shift",
+            _ => token,
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+#[derive(Clone, Copy)]
+enum DisplayStyle {
+    MacOS,
+    Windows,
+    Linux,
+}
+
+impl DisplayStyle {
+    fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::MacOS
+        } else if cfg!(windows) {
+            Self::Windows
+        } else {
+            Self::Linux
+        }
+    }
+}
+
+/// Renders the shortcut the way the current platform displays shortcuts in its menus,
+/// e.g. `⌃⇧A` on macOS and `Ctrl+Shift+A` on Windows and Linux.
+pub fn display_string(shortcut: &Shortcut) -> String {
+    display_string_with_style(shortcut, DisplayStyle::current())
+}
+
+fn display_string_with_style(shortcut: &Shortcut, style: DisplayStyle) -> String {
+    let (modifiers, separator) = match style {
+        DisplayStyle::MacOS => (
+            [
+                (Modifiers::CONTROL, "⌃"),
+                (Modifiers::ALT, "⌥"),
+                (Modifiers::SHIFT, "⇧"),
+                (Modifiers::SUPER, "⌘"),
+            ],
+            "",
+        ),
+        DisplayStyle::Windows => (
+            [
+                (Modifiers::CONTROL, "Ctrl"),
+                (Modifiers::ALT, "Alt"),
+                (Modifiers::SHIFT, "Shift"),
+                (Modifiers::SUPER, "Win"),
+            ],
+            "+",
+        ),
+        DisplayStyle::Linux => (
+            [
+                (Modifiers::CONTROL, "Ctrl"),
+                (Modifiers::ALT, "Alt"),
+                (Modifiers::SHIFT, "Shift"),
+                (Modifiers::SUPER, "Super"),
+            ],
+            "+",
+        ),
+    };
+
+    let key = match (shortcut.key, style) {
+        (Code::ArrowUp, DisplayStyle::MacOS) => "↑".into(),
+        (Code::ArrowDown, DisplayStyle::MacOS) => "↓".into(),
+        (Code::ArrowLeft, DisplayStyle::MacOS) => "←".into(),
+        (Code::ArrowRight, DisplayStyle::MacOS) => "→".into(),
+        (Code::Enter, DisplayStyle::MacOS) => "↩".into(),
+        (Code::Backspace, DisplayStyle::MacOS) => "⌫".into(),
+        (Code::Delete, DisplayStyle::MacOS) => "⌦".into(),
+        (Code::Escape, DisplayStyle::MacOS) => "⎋".into(),
+        (Code::Tab, DisplayStyle::MacOS) => "⇥".into(),
+        (key, _) => {
+            let key = key.to_string();
+            match key
+                .strip_prefix("Key")
+                .or_else(|| key.strip_prefix("Digit"))
+                .or_else(|| key.strip_prefix("Arrow"))
+            {
+                Some(key) => key.to_string(),
+                None => key,
+            }
+        }
+    };
+
+    modifiers
+        .iter()
+        .filter(|(modifier, _)| shortcut.mods.contains(*modifier))
+        .map(|(_, symbol)| *symbol)
+        .chain(std::iter::once(key.as_str()))
+        .collect::<Vec<_>>()
+        .join(separator)
 }
 
 fn try_into_shortcut<S: TryInto<ShortcutWrapper>>(shortcut: S) -> Result<Shortcut>
@@ -270,6 +557,33 @@ struct ShortcutJsEvent {
     shortcut: String,
     id: u32,
     state: ShortcutState,
+    /// Modifiers held when the event was triggered.
+    modifiers: Vec<&'static str>,
+    /// Key of the triggering shortcut, e.g. `KeyA`.
+    code: String,
+}
+
+impl ShortcutJsEvent {
+    fn new(shortcut: &Shortcut, e: ShortcutEvent, held: Modifiers) -> Self {
+        let modifiers = [
+            (Modifiers::SHIFT, "Shift"),
+            (Modifiers::CONTROL, "Control"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::SUPER, "Super"),
+        ]
+        .into_iter()
+        .filter(|(modifier, _)| held.contains(*modifier))
+        .map(|(_, name)| name)
+        .collect();
+
+        Self {
+            id: e.id,
+            state: e.state,
+            shortcut: shortcut.into_string(),
+            modifiers,
+            code: shortcut.key.to_string(),
+        }
+    }
 }
 
 #[tauri::command]
@@ -292,12 +606,8 @@ fn register<R: Runtime>(
         hotkeys,
         Some(
             move |_app: &AppHandle<R>, shortcut: &Shortcut, e: ShortcutEvent| {
-                let js_event = ShortcutJsEvent {
-                    id: e.id,
-                    state: e.state,
-                    shortcut: shortcut.into_string(),
-                };
-                let _ = handler.send(js_event);
+                let modifiers = current_modifiers().unwrap_or(shortcut.mods);
+                let _ = handler.send(ShortcutJsEvent::new(shortcut, e, modifiers));
             },
         ),
     )
@@ -324,6 +634,14 @@ fn unregister_all<R: Runtime>(
     global_shortcut.unregister_all()
 }
 
+#[tauri::command]
+fn reregister_all<R: Runtime>(
+    _app: AppHandle<R>,
+    global_shortcut: State<'_, GlobalShortcut<R>>,
+) -> Result<()> {
+    global_shortcut.reregister_all()
+}
+
 #[tauri::command]
 fn is_registered<R: Runtime>(
     _app: AppHandle<R>,
@@ -333,9 +651,67 @@ fn is_registered<R: Runtime>(
     Ok(global_shortcut.is_registered(parse_shortcut(shortcut)?))
 }
 
+/// Registers the shortcuts given to the [`Builder`].
+///
+/// In lenient mode, registration errors are logged and collected instead of aborting.
+fn register_shortcuts<F>(
+    shortcuts: Vec<Shortcut>,
+    lenient: bool,
+    mut register: F,
+) -> Result<(Vec<Shortcut>, Vec<FailedRegistration>)>
+where
+    F: FnMut(Shortcut) -> Result<()>,
+{
+    let mut registered = Vec::new();
+    let mut failed = Vec::new();
+    for shortcut in shortcuts {
+        match register(shortcut) {
+            Ok(()) => registered.push(shortcut),
+            Err(error) if lenient => {
+                log::error!(
+                    "failed to register shortcut {}: {error}",
+                    shortcut.into_string()
+                );
+                failed.push(FailedRegistration { shortcut, error });
+            }
+            Err(error) => return Err(error),
+        }
+    }
+    Ok((registered, failed))
+}
+
+/// Unregisters then registers each shortcut, returning the first error after trying them all.
+///
+/// Unregistering fails if the system already dropped the shortcut, which is ignored.
+fn reregister_shortcuts<U, F>(
+    shortcuts: Vec<Shortcut>,
+    mut unregister: U,
+    mut register: F,
+) -> Result<()>
+where
+    U: FnMut(Shortcut) -> Result<()>,
+    F: FnMut(Shortcut) -> Result<()>,
+{
+    let mut result = Ok(());
+    for shortcut in shortcuts {
+        let _ = unregister(shortcut);
+        if let Err(error) = register(shortcut) {
+            log::error!(
+                "failed to register shortcut {} again: {error}",
+                shortcut.into_string()
+            );
+            if result.is_ok() {
+                result = Err(error);
+            }
+        }
+    }
+    result
+}
+
 pub struct Builder<R: Runtime> {
     shortcuts: Vec<Shortcut>,
     handler: Option<HandlerFn<R>>,
+    lenient: bool,
 }
 
 impl<R: Runtime> Default for Builder<R> {
@@ -343,6 +719,7 @@ impl<R: Runtime> Default for Builder<R> {
         Self {
             shortcuts: Vec::new(),
             handler: Default::default(),
+            lenient: false,
         }
     }
 }
@@ -385,26 +762,42 @@ impl<R: Runtime> Builder<R> {
         self
     }
 
+    /// Whether a shortcut that fails to register should be skipped instead of failing the plugin setup.
+    ///
+    /// The failures are logged and can be retrieved with [`GlobalShortcut::failed_registrations`].
+    /// Defaults to `false`.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
     pub fn build(self) -> TauriPlugin<R> {
         let handler = self.handler;
         let shortcuts = self.shortcuts;
+        let lenient = self.lenient;
         PluginBuilder::new("global-shortcut")
             .invoke_handler(tauri::generate_handler![
                 register,
                 unregister,
                 unregister_all,
+                reregister_all,
                 is_registered,
             ])
             .setup(move |app, _api| {
                 let manager = global_hotkey::GlobalHotKeyManager::new()?;
+                let (registered, failed_registrations) =
+                    register_shortcuts(shortcuts, lenient, |shortcut| {
+                        manager.register(shortcut).map_err(Into::into)
+                    })?;
                 let mut store = HashMap::<HotKeyId, RegisteredShortcut<R>>::new();
-                for shortcut in shortcuts {
-                    manager.register(shortcut)?;
+                for shortcut in registered {
                     store.insert(
                         shortcut.id(),
                         RegisteredShortcut {
                             shortcut,
                             handler: None,
+                            debounce: None,
+                            scope: ShortcutScope::Global,
                         },
                     );
                 }
@@ -413,8 +806,27 @@ impl<R: Runtime> Builder<R> {
                 let shortcuts_ = shortcuts.clone();
 
                 let app_handle = app.clone();
+                let focused_window = {
+                    let app_handle = app_handle.clone();
+                    move || {
+                        app_handle
+                            .webview_windows()
+                            .into_values()
+                            .find(|window| window.is_focused().unwrap_or(false))
+                            .map(|window| window.label().to_string())
+                    }
+                };
                 GlobalHotKeyEvent::set_event_handler(Some(move |e: GlobalHotKeyEvent| {
                     if let Some(shortcut) = shortcuts_.lock().unwrap().get(&e.id) {
+                        if !should_dispatch(
+                            &shortcut.scope,
+                            shortcut.debounce.as_ref(),
+                            e.state,
+                            Instant::now(),
+                            &focused_window,
+                        ) {
+                            return;
+                        }
                         if let Some(handler) = &shortcut.handler {
                             handler(&app_handle, &shortcut.shortcut, e);
                         }
@@ -428,9 +840,278 @@ impl<R: Runtime> Builder<R> {
                     app: app.clone(),
                     manager: Arc::new(GlobalHotKeyManager(manager)),
                     shortcuts,
+                    failed_registrations,
                 });
                 Ok(())
             })
             .build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn register_all_but(rejected: Shortcut) -> impl FnMut(Shortcut) -> Result<()> {
+        move |shortcut| {
+            if shortcut == rejected {
+                Err(Error::GlobalHotkey("already registered".into()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn lenient_registration_skips_failed_shortcuts() {
+        let valid: Shortcut = "CmdOrCtrl+Shift+A".parse().unwrap();
+        let invalid: Shortcut = "CmdOrCtrl+Shift+B".parse().unwrap();
+
+        let (registered, failed) =
+            register_shortcuts(vec![valid, invalid], true, register_all_but(invalid)).unwrap();
+
+        assert_eq!(registered, [valid]);
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].shortcut, invalid);
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "linux",
+        ignore = "the system global shortcut manager needs an X11 display"
+    )]
+    fn lenient_plugin_setup_reports_failed_shortcuts() {
+        let shortcut: Shortcut = "CmdOrCtrl+Alt+Shift+F20".parse().unwrap();
+        // the second registration of the same shortcut conflicts with the first one
+        let app = tauri::test::mock_builder()
+            .plugin(
+                Builder::new()
+                    .with_shortcuts([shortcut, shortcut])
+                    .unwrap()
+                    .lenient(true)
+                    .build(),
+            )
+            .build(tauri::test::mock_context(tauri::test::noop_assets()))
+            .unwrap();
+        let global_shortcut = app.global_shortcut();
+
+        assert!(global_shortcut.is_registered(shortcut));
+        let failed = global_shortcut.failed_registrations();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].shortcut, shortcut);
+
+        global_shortcut.unregister_all().unwrap();
+    }
+
+    #[test]
+    fn parses_modifier_aliases() {
+        let expected = Shortcut::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyK);
+        for alias in [
+            "Cmd+Shift+K",
+            "Super+Shift+K",
+            "Win+Shift+K",
+            "windows+shift+k",
+            "Meta+Shift+K",
+            "⌘+⇧+K",
+        ] {
+            assert_eq!(parse_shortcut(alias).unwrap(), expected, "{alias}");
+        }
+
+        let expected = Shortcut::new(Some(Modifiers::ALT | Modifiers::CONTROL), Code::KeyK);
+        for alias in ["Alt+Ctrl+K", "Option+Control+K", "Opt+Ctrl+K", "⌥+⌃+K"] {
+            assert_eq!(parse_shortcut(alias).unwrap(), expected, "{alias}");
+        }
+
+        assert!(try_into_shortcut("Meta+K").is_ok());
+        assert!(parse_shortcut("Hyper+K").is_err());
+    }
+
+    #[test]
+    fn displays_shortcuts_per_platform() {
+        let shortcut = Shortcut::new(
+            Some(Modifiers::SUPER | Modifiers::SHIFT | Modifiers::ALT | Modifiers::CONTROL),
+            Code::KeyA,
+        );
+        assert_eq!(
+            display_string_with_style(&shortcut, DisplayStyle::MacOS),
+            "⌃⌥⇧⌘A"
+        );
+        assert_eq!(
+            display_string_with_style(&shortcut, DisplayStyle::Windows),
+            "Ctrl+Alt+Shift+Win+A"
+        );
+        assert_eq!(
+            display_string_with_style(&shortcut, DisplayStyle::Linux),
+            "Ctrl+Alt+Shift+Super+A"
+        );
+
+        let shortcut = Shortcut::new(Some(Modifiers::SUPER), Code::ArrowUp);
+        assert_eq!(
+            display_string_with_style(&shortcut, DisplayStyle::MacOS),
+            "⌘↑"
+        );
+        assert_eq!(
+            display_string_with_style(&shortcut, DisplayStyle::Windows),
+            "Win+Up"
+        );
+
+        let shortcut = Shortcut::new(None, Code::Digit1);
+        assert_eq!(
+            display_string_with_style(&shortcut, DisplayStyle::Linux),
+            "1"
+        );
+
+        #[cfg(target_os = "macos")]
+        assert_eq!(display_string(&"CmdOrCtrl+S".parse().unwrap()), "⌘S");
+        #[cfg(not(target_os = "macos"))]
+        assert_eq!(display_string(&"CmdOrCtrl+S".parse().unwrap()), "Ctrl+S");
+    }
+
+    #[test]
+    fn debounce_drops_repeated_presses() {
+        let debounce = Debounce::new(Duration::from_millis(100));
+        let start = Instant::now();
+
+        // auto-repeat sends a `Pressed` event every 10ms while the shortcut is held for 250ms
+        let fired = (0..25)
+            .filter(|i| {
+                debounce.should_fire(
+                    ShortcutState::Pressed,
+                    start + Duration::from_millis(i * 10),
+                )
+            })
+            .count();
+        assert_eq!(fired, 3);
+
+        // releasing the shortcut lets the next press through right away
+        let released = start + Duration::from_millis(250);
+        assert!(debounce.should_fire(ShortcutState::Released, released));
+        assert!(debounce.should_fire(ShortcutState::Pressed, released));
+        assert!(!debounce.should_fire(ShortcutState::Pressed, released));
+    }
+
+    #[test]
+    fn scoped_shortcuts_are_skipped_out_of_scope() {
+        let now = Instant::now();
+        let dispatch = |scope: &ShortcutScope, focused: Option<&str>| {
+            should_dispatch(scope, None, ShortcutState::Pressed, now, || {
+                focused.map(Into::into)
+            })
+        };
+
+        assert!(!dispatch(&ShortcutScope::AppFocused, None));
+        assert!(dispatch(&ShortcutScope::AppFocused, Some("main")));
+
+        let settings = ShortcutScope::Window("settings".into());
+        assert!(!dispatch(&settings, None));
+        assert!(!dispatch(&settings, Some("main")));
+        assert!(dispatch(&settings, Some("settings")));
+
+        assert!(dispatch(&ShortcutScope::Global, None));
+    }
+
+    #[test]
+    fn out_of_scope_presses_are_not_debounced() {
+        let debounce = Debounce::new(Duration::from_millis(100));
+        let now = Instant::now();
+
+        assert!(!should_dispatch(
+            &ShortcutScope::AppFocused,
+            Some(&debounce),
+            ShortcutState::Pressed,
+            now,
+            || None
+        ));
+        // the press received while unfocused doesn't hold back the next one
+        assert!(should_dispatch(
+            &ShortcutScope::AppFocused,
+            Some(&debounce),
+            ShortcutState::Pressed,
+            now,
+            || Some("main".into())
+        ));
+    }
+
+    #[test]
+    fn events_report_the_held_modifiers() {
+        let shortcut: Shortcut = "Ctrl+Shift+A".parse().unwrap();
+        let event = ShortcutEvent {
+            id: shortcut.id(),
+            state: ShortcutState::Pressed,
+        };
+
+        // Alt is held on top of the registered combo
+        let held = Modifiers::CONTROL | Modifiers::SHIFT | Modifiers::ALT;
+        let event = serde_json::to_value(ShortcutJsEvent::new(&shortcut, event, held)).unwrap();
+        assert_eq!(
+            event["modifiers"],
+            serde_json::json!(["Shift", "Control", "Alt"])
+        );
+        assert_eq!(event["code"], "KeyA");
+        assert_eq!(event["shortcut"], "shift+control+KeyA");
+    }
+
+    #[test]
+    #[ignore = "grabs F13-F15 from the system global shortcut manager, which needs a desktop session"]
+    fn reregisters_all_shortcuts() {
+        let app = tauri::test::mock_builder()
+            .plugin(Builder::new().build())
+            .build(tauri::test::mock_context(tauri::test::noop_assets()))
+            .unwrap();
+        let global_shortcut = app.global_shortcut();
+
+        let shortcuts: Vec<Shortcut> = ["CmdOrCtrl+Shift+F13", "CmdOrCtrl+Shift+F14", "Alt+F15"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        for shortcut in &shortcuts {
+            global_shortcut
+                .on_shortcut(*shortcut, |_, _, _| {})
+                .unwrap();
+        }
+        // the system dropped the second one
+        global_shortcut.manager.0.unregister(shortcuts[1]).unwrap();
+
+        global_shortcut.reregister_all().unwrap();
+
+        let registered = global_shortcut.shortcuts.lock().unwrap();
+        assert_eq!(registered.len(), shortcuts.len());
+        for shortcut in &shortcuts {
+            assert!(registered[&shortcut.id()].handler.is_some());
+            // registered with the system again
+            assert!(global_shortcut.manager.0.register(*shortcut).is_err());
+        }
+        drop(registered);
+
+        global_shortcut.unregister_all().unwrap();
+    }
+
+    #[test]
+    fn reregistration_goes_on_after_an_error() {
+        let valid: Shortcut = "CmdOrCtrl+Shift+A".parse().unwrap();
+        let invalid: Shortcut = "CmdOrCtrl+Shift+B".parse().unwrap();
+        let mut registered = Vec::new();
+
+        let result = reregister_shortcuts(
+            vec![invalid, valid],
+            |_| Ok(()),
+            |shortcut| {
+                register_all_but(invalid)(shortcut)?;
+                registered.push(shortcut);
+                Ok(())
+            },
+        );
+        assert!(result.is_err());
+        assert_eq!(registered, [valid]);
+    }
+
+    #[test]
+    fn strict_registration_fails_on_first_error() {
+        let valid: Shortcut = "CmdOrCtrl+Shift+A".parse().unwrap();
+        let invalid: Shortcut = "CmdOrCtrl+Shift+B".parse().unwrap();
+
+        assert!(
+            register_shortcuts(vec![valid, invalid], false, register_all_but(invalid)).is_err()
+        );
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::Modifiers;
+
+/// Returns the modifiers currently held on the keyboard,
+/// or `None` if they can't be read, e.g. on Wayland.
+pub fn current_modifiers() -> Option<Modifiers> {
+    imp::current_modifiers()
+}
+
+#[cfg(windows)]
+mod imp {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+        GetAsyncKeyState, VIRTUAL_KEY, VK_CONTROL, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT,
+    };
+
+    use crate::Modifiers;
+
+    pub fn current_modifiers() -> Option<Modifiers> {
+        // the most significant bit is set while the key is down
+        let is_down = |key: VIRTUAL_KEY| unsafe { GetAsyncKeyState(key as i32) } < 0;
+
+        let mut modifiers = Modifiers::empty();
+        modifiers.set(Modifiers::SHIFT, is_down(VK_SHIFT));
+        modifiers.set(Modifiers::CONTROL, is_down(VK_CONTROL));
+        modifiers.set(Modifiers::ALT, is_down(VK_MENU));
+        modifiers.set(Modifiers::SUPER, is_down(VK_LWIN) || is_down(VK_RWIN));
+        Some(modifiers)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use objc2_app_kit::{NSEvent, NSEventModifierFlags};
+
+    use crate::Modifiers;
+
+    pub fn current_modifiers() -> Option<Modifiers> {
+        let flags = unsafe { NSEvent::modifierFlags_class() };
+
+        let mut modifiers = Modifiers::empty();
+        modifiers.set(
+            Modifiers::SHIFT,
+            flags.contains(NSEventModifierFlags::Shift),
+        );
+        modifiers.set(
+            Modifiers::CONTROL,
+            flags.contains(NSEventModifierFlags::Control),
+        );
+        modifiers.set(Modifiers::ALT, flags.contains(NSEventModifierFlags::Option));
+        modifiers.set(
+            Modifiers::SUPER,
+            flags.contains(NSEventModifierFlags::Command),
+        );
+        Some(modifiers)
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod imp {
+    use std::sync::OnceLock;
+
+    use x11rb::{
+        connection::Connection,
+        protocol::xproto::{ConnectionExt, KeyButMask},
+        rust_connection::RustConnection,
+    };
+
+    use crate::Modifiers;
+
+    pub fn current_modifiers() -> Option<Modifiers> {
+        // global-hotkey only supports X11, so there's nothing to read without an X server
+        static CONNECTION: OnceLock<Option<(RustConnection, u32)>> = OnceLock::new();
+        let (connection, root) = CONNECTION
+            .get_or_init(|| {
+                let (connection, screen) = x11rb::connect(None).ok()?;
+                let root = connection.setup().roots.get(screen)?.root;
+                Some((connection, root))
+            })
+            .as_ref()?;
+
+        let mask = u16::from(connection.query_pointer(*root).ok()?.reply().ok()?.mask);
+        let is_down = |modifier: KeyButMask| mask & u16::from(modifier) != 0;
+
+        let mut modifiers = Modifiers::empty();
+        modifiers.set(Modifiers::SHIFT, is_down(KeyButMask::SHIFT));
+        modifiers.set(Modifiers::CONTROL, is_down(KeyButMask::CONTROL));
+        modifiers.set(Modifiers::ALT, is_down(KeyButMask::MOD1));
+        modifiers.set(Modifiers::SUPER, is_down(KeyButMask::MOD4));
+        Some(modifiers)
+    }
+}
@@ -2,11 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use std::path::PathBuf;
+
 #[path = "src/scope.rs"]
 #[allow(dead_code)]
 mod scope;
 
-const COMMANDS: &[&str] = &["fetch", "fetch_cancel", "fetch_send", "fetch_read_body"];
+const COMMANDS: &[&str] = &[
+    "fetch",
+    "fetch_cancel",
+    "fetch_cancel_all",
+    "set_cookie",
+    "get_cookies",
+    "fetch_send",
+    "fetch_read_body",
+    "fetch_download",
+];
 
 /// HTTP scope entry.
 #[derive(schemars::JsonSchema)]
@@ -45,6 +56,13 @@ enum HttpScopeEntry {
         /// - "https://myapi.service.com/users/*": allows access to any URLs that begins with "https://myapi.service.com/users/"
         url: String,
     },
+    Path {
+        /// A file that can be sent in the multipart bodies of the requests,
+        /// in addition to the files allowed by the fs plugin scope.
+        ///
+        /// Glob patterns and path variables are supported, e.g. "$DOCUMENT/uploads/*".
+        path: PathBuf,
+    },
 }
 
 // Ensure `HttpScopeEntry` and `scope::EntryRaw` is kept in sync
@@ -52,10 +70,12 @@ fn _f() {
     match scope::EntryRaw::Value(String::new()) {
         scope::EntryRaw::Value(url) => HttpScopeEntry::Value(url),
         scope::EntryRaw::Object { url } => HttpScopeEntry::Object { url },
+        scope::EntryRaw::Path { path } => HttpScopeEntry::Path { path },
     };
     match HttpScopeEntry::Value(String::new()) {
         HttpScopeEntry::Value(url) => scope::EntryRaw::Value(url),
         HttpScopeEntry::Object { url } => scope::EntryRaw::Object { url },
+        HttpScopeEntry::Path { path } => scope::EntryRaw::Path { path },
     };
 }
 
@@ -88,8 +88,147 @@ export interface ClientOptions {
    * Configuration for dangerous settings on the client such as disabling SSL verification.
    */
   danger?: DangerousSettings
+  /**
+   * Client certificate presented to servers that request one, for mutual TLS authentication.
+   *
+   * @since 2.6.0
+   */
+  clientIdentity?: ClientIdentity
+  /**
+   * Do not set the `Origin` header automatically for this request.
+   *
+   * @since 2.6.0
+   */
+  omitOrigin?: boolean
+  /**
+   * Do not set the `User-Agent` header automatically for this request.
+   *
+   * @since 2.6.0
+   */
+  omitUserAgent?: boolean
+  /**
+   * Maximum size in bytes of the response body, including `data:` URLs.
+   * Larger responses are rejected instead of being read into memory.
+   *
+   * @since 2.6.0
+   */
+  maxResponseSize?: number
+  /**
+   * Cache the body of `GET` responses with an `ETag` or `Last-Modified` header,
+   * and revalidate it on the next request to the same URL.
+   *
+   * When the server answers `304 Not Modified`, the cached response is returned instead
+   * and its `fromCache` property is set to `true`.
+   *
+   * Responses are cached per URL and per value of the request headers listed in their `Vary` header.
+   * Responses to requests with an `Authorization` header, with `Cache-Control: no-store` or with `Vary: *`
+   * are not cached. The cache keeps up to 100 responses and 16 MiB of bodies, evicting the least recently used first.
+   *
+   * @since 2.6.0
+   */
+  responseCache?: boolean
+  /**
+   * Connect to the given addresses instead of resolving the hostnames through DNS.
+   * The `Host` header and TLS server name still use the hostname.
+   *
+   * @since 2.6.0
+   */
+  resolve?: HostMapping[]
+  /**
+   * Send a `multipart/form-data` body built from these parts.
+   * The request fails if it also has a `body`.
+   * File parts are streamed from disk and must be allowed by the scope of the fs plugin,
+   * or by a `{ "path": "..." }` entry of the `http:allow-fetch` scope, and not denied by one.
+   *
+   * Requires the `multipart` feature of the Rust crate.
+   *
+   * @since 2.6.0
+   */
+  multipart?: MultipartPart[]
+  /**
+   * Called while the `multipart` body is being sent.
+   *
+   * @since 2.6.0
+   */
+  onUploadProgress?: (progress: UploadProgress) => void
+  /**
+   * Stream the request body without a `Content-Length` header,
+   * it is then sent with `Transfer-Encoding: chunked` over HTTP/1.1.
+   *
+   * `Content-Length` and `Transfer-Encoding` are forbidden headers that are dropped from `headers`
+   * unless the `unsafe-headers` feature of the Rust crate is enabled. Even then, a `Content-Length`
+   * header is removed from chunked requests, and the `Transfer-Encoding` header is set by the client.
+   *
+   * @since 2.6.0
+   */
+  chunked?: boolean
+}
+
+/**
+ * A part of a `multipart/form-data` request body, either held in memory (`value`)
+ * or streamed from a file (`path`).
+ *
+ * @example
+ * ```typescript
+ * const parts: MultipartPart[] = [
+ *   { name: 'metadata', value: JSON.stringify({ title: 'video' }), contentType: 'application/json' },
+ *   { name: 'file', path: '/home/user/video.mp4', contentType: 'video/mp4' }
+ * ]
+ * ```
+ *
+ * @since 2.6.0
+ */
+export type MultipartPart = {
+  /** The name of the form field. */
+  name: string
+  /** The file name of the part, defaults to the name of the file for `path` parts. */
+  fileName?: string
+  /** The `Content-Type` of the part, defaults to `application/octet-stream` for `path` parts. */
+  contentType?: string
+} & (
+  | { value: string | Uint8Array | ArrayBuffer | number[] }
+  | { path: string }
+)
+
+/**
+ * Progress of a request body upload.
+ *
+ * @since 2.6.0
+ */
+export interface UploadProgress {
+  /** Number of bytes sent so far. */
+  sent: number
+  /** Total size of the body in bytes. */
+  total: number
 }
 
+/**
+ * Overrides the DNS resolution of a hostname.
+ *
+ * @since 2.6.0
+ */
+export interface HostMapping {
+  /** The hostname to override, e.g. `api.example.com`. */
+  host: string
+  /**
+   * The socket address to connect to, e.g. `127.0.0.1:8080`.
+   * A port in the request URL takes precedence, use port `0` for the default port of the URL scheme.
+   */
+  addr: string
+}
+
+/**
+ * A client certificate and its private key, either as a PEM bundle holding the certificate chain
+ * (leaf first) and the private key, or as a DER encoded PKCS #12 archive.
+ *
+ * PKCS #12 archives require one of the `native-tls` features of the Rust crate.
+ *
+ * @since 2.6.0
+ */
+export type ClientIdentity =
+  | { pem: string }
+  | { pkcs12: Uint8Array | ArrayBuffer | number[]; password?: string }
+
 /**
  * Configuration for dangerous settings on the client such as disabling SSL verification.
  *
@@ -108,24 +247,23 @@ export interface DangerousSettings {
 
 const ERROR_REQUEST_CANCELLED = 'Request cancelled'
 
+interface FetchSendResponse {
+  status: number
+  statusText: string
+  headers: [[string, string]]
+  url: string
+  rid: number
+  fromCache: boolean
+  httpVersion: string | null
+}
+
 /**
- * Fetch a resource from the network. It returns a `Promise` that resolves to the
- * `Response` to that `Request`, whether it is successful or not.
- *
- * @example
- * ```typescript
- * const response = await fetch("http://my.json.host/data.json");
- * console.log(response.status);  // e.g. 200
- * console.log(response.statusText); // e.g. "OK"
- * const jsonData = await response.json();
- * ```
- *
- * @since 2.0.0
+ * Starts the request and waits for the response headers.
  */
-export async function fetch(
+async function send(
   input: URL | Request | string,
   init?: RequestInit & ClientOptions
-): Promise<Response> {
+): Promise<FetchSendResponse> {
   // abort early here if needed
   const signal = init?.signal
   if (signal?.aborted) {
@@ -136,6 +274,34 @@ export async function fetch(
   const connectTimeout = init?.connectTimeout
   const proxy = init?.proxy
   const danger = init?.danger
+  const clientIdentity =
+    init?.clientIdentity && 'pkcs12' in init.clientIdentity
+      ? {
+          pkcs12: Array.from(new Uint8Array(init.clientIdentity.pkcs12)),
+          password: init.clientIdentity.password
+        }
+      : init?.clientIdentity
+  const omitOrigin = init?.omitOrigin
+  const omitUserAgent = init?.omitUserAgent
+  const maxResponseSize = init?.maxResponseSize
+  const responseCache = init?.responseCache
+  const resolve = init?.resolve
+  const multipart = init?.multipart?.map(({ name, fileName, contentType, ...body }) => ({
+    name,
+    fileName,
+    contentType,
+    ...('path' in body
+      ? { path: body.path }
+      : {
+          data: Array.from(
+            typeof body.value === 'string'
+              ? new TextEncoder().encode(body.value)
+              : new Uint8Array(body.value)
+          )
+        })
+  }))
+  const onUploadProgress = init?.onUploadProgress
+  const chunked = init?.chunked
 
   // Remove these fields before creating the request
   if (init) {
@@ -143,6 +309,15 @@ export async function fetch(
     delete init.connectTimeout
     delete init.proxy
     delete init.danger
+    delete init.clientIdentity
+    delete init.omitOrigin
+    delete init.omitUserAgent
+    delete init.maxResponseSize
+    delete init.responseCache
+    delete init.resolve
+    delete init.multipart
+    delete init.onUploadProgress
+    delete init.chunked
   }
 
   const headers = init?.headers
@@ -186,6 +361,12 @@ export async function fetch(
     throw new Error(ERROR_REQUEST_CANCELLED)
   }
 
+  let uploadProgressChannel: Channel<UploadProgress> | undefined
+  if (onUploadProgress) {
+    uploadProgressChannel = new Channel<UploadProgress>()
+    uploadProgressChannel.onmessage = onUploadProgress
+  }
+
   const rid = await invoke<number>('plugin:http|fetch', {
     clientConfig: {
       method: req.method,
@@ -195,8 +376,17 @@ export async function fetch(
       maxRedirections,
       connectTimeout,
       proxy,
-      danger
-    }
+      danger,
+      clientIdentity,
+      omitOrigin,
+      omitUserAgent,
+      maxResponseSize,
+      responseCache,
+      resolve,
+      multipart,
+      chunked
+    },
+    onUploadProgress: uploadProgressChannel
   })
 
   const abort = () => invoke('plugin:http|fetch_cancel', { rid })
@@ -211,23 +401,70 @@ export async function fetch(
 
   signal?.addEventListener('abort', () => void abort())
 
-  interface FetchSendResponse {
-    status: number
-    statusText: string
-    headers: [[string, string]]
-    url: string
-    rid: number
-  }
+  return await invoke<FetchSendResponse>('plugin:http|fetch_send', {
+    rid
+  })
+}
 
-  const {
+/**
+ * Creates a `Response` with the metadata of `response`.
+ */
+function toResponse(
+  body: ReadableStream | null,
+  {
     status,
     statusText,
     url,
-    headers: responseHeaders,
-    rid: responseRid
-  } = await invoke<FetchSendResponse>('plugin:http|fetch_send', {
-    rid
+    headers,
+    fromCache,
+    httpVersion
+  }: FetchSendResponse
+): Response {
+  const res = new Response(body, {
+    status,
+    statusText
+  })
+
+  // Set `Response` properties that are ignored by the
+  // constructor, like url and some headers
+  //
+  // Since url and headers are read only properties
+  // this is the only way to set them.
+  Object.defineProperty(res, 'url', { value: url })
+  Object.defineProperty(res, 'headers', {
+    value: new Headers(headers)
   })
+  Object.defineProperty(res, 'fromCache', { value: fromCache })
+  Object.defineProperty(res, 'httpVersion', { value: httpVersion })
+
+  return res
+}
+
+/**
+ * Fetch a resource from the network. It returns a `Promise` that resolves to the
+ * `Response` to that `Request`, whether it is successful or not.
+ *
+ * Responses also have an `httpVersion` property with the HTTP version of the connection,
+ * e.g. `HTTP/2` or `HTTP/1.1`, which over TLS is the protocol negotiated through ALPN.
+ * It is `null` for responses served from the response cache and for `data:` URLs.
+ *
+ * @example
+ * ```typescript
+ * const response = await fetch("http://my.json.host/data.json");
+ * console.log(response.status);  // e.g. 200
+ * console.log(response.statusText); // e.g. "OK"
+ * const jsonData = await response.json();
+ * ```
+ *
+ * @since 2.0.0
+ */
+export async function fetch(
+  input: URL | Request | string,
+  init?: RequestInit & ClientOptions
+): Promise<Response> {
+  const signal = init?.signal
+  const response = await send(input, init)
+  const { status, rid: responseRid } = response
 
   // no body for 101, 103, 204, 205 and 304
   // see https://fetch.spec.whatwg.org/#null-body-status
@@ -266,20 +503,151 @@ export async function fetch(
         }
       })
 
-  const res = new Response(body, {
-    status,
-    statusText
+  return toResponse(body, response)
+}
+
+/**
+ * Progress of a response body download.
+ *
+ * @since 2.6.0
+ */
+export interface DownloadProgress {
+  /** Number of bytes written to the file so far. */
+  received: number
+  /** Size of the body from the `Content-Length` header, if known. */
+  total: number | null
+}
+
+/**
+ * Options of {@link download}.
+ *
+ * @since 2.6.0
+ */
+export interface DownloadOptions {
+  /** Called after each chunk of the body is written to the file. */
+  onProgress?: (progress: DownloadProgress) => void
+}
+
+/**
+ * Fetches a resource and writes the response body straight to a file,
+ * without reading it in the webview. The file is replaced if it exists.
+ *
+ * The path must be allowed by the scope of the fs plugin.
+ * The returned `Response` has no body, and is only resolved once the file is written.
+ *
+ * @example
+ * ```typescript
+ * const response = await download("http://my.host/video.mp4", "/home/user/video.mp4", {
+ *   onProgress: ({ received, total }) => console.log(`${received}/${total}`)
+ * });
+ * console.log(response.status);
+ * ```
+ *
+ * @since 2.6.0
+ */
+export async function download(
+  input: URL | Request | string,
+  path: string,
+  init?: RequestInit & ClientOptions & DownloadOptions
+): Promise<Response> {
+  const onProgress = init?.onProgress
+  if (init) {
+    delete init.onProgress
+  }
+
+  const response = await send(input, init)
+
+  const onProgressChannel = new Channel<DownloadProgress>()
+  if (onProgress) {
+    onProgressChannel.onmessage = onProgress
+  }
+
+  await invoke('plugin:http|fetch_download', {
+    rid: response.rid,
+    path,
+    onProgress: onProgressChannel
   })
 
-  // Set `Response` properties that are ignored by the
-  // constructor, like url and some headers
-  //
-  // Since url and headers are read only properties
-  // this is the only way to set them.
-  Object.defineProperty(res, 'url', { value: url })
-  Object.defineProperty(res, 'headers', {
-    value: new Headers(responseHeaders)
+  return toResponse(null, response)
+}
+
+/**
+ * Cancels all the requests in flight, as if each of their `signal`s was aborted.
+ *
+ * @example
+ * ```typescript
+ * window.addEventListener("beforeunload", () => cancelAll());
+ * ```
+ *
+ * @returns the number of requests cancelled.
+ *
+ * @since 2.6.0
+ */
+export async function cancelAll(): Promise<number> {
+  return await invoke('plugin:http|fetch_cancel_all')
+}
+
+/**
+ * Attributes of a cookie set with {@link setCookie}, like those of a `Set-Cookie` header.
+ *
+ * @since 2.6.0
+ */
+export interface CookieAttributes {
+  /** Also send the cookie to the subdomains of this domain, instead of the host of the URL only. */
+  domain?: string
+  path?: string
+  /** Number of seconds until the cookie expires. The cookie is only persisted if set. */
+  maxAge?: number
+  secure?: boolean
+  httpOnly?: boolean
+  sameSite?: 'strict' | 'lax' | 'none'
+}
+
+/**
+ * A cookie sent along requests, see {@link getCookies}.
+ *
+ * @since 2.6.0
+ */
+export interface Cookie {
+  name: string
+  value: string
+}
+
+/**
+ * Stores a cookie as if a response from `url` had set it, so it is sent along the next requests.
+ *
+ * Requires the `cookies` feature of the plugin, the `http:allow-set-cookie` permission
+ * and `url` to be allowed by the scope.
+ *
+ * @example
+ * ```typescript
+ * await setCookie("https://api.my.host", "session", token, { maxAge: 3600, secure: true });
+ * ```
+ *
+ * @since 2.6.0
+ */
+export async function setCookie(
+  url: URL | string,
+  name: string,
+  value: string,
+  attributes?: CookieAttributes
+): Promise<void> {
+  await invoke('plugin:http|set_cookie', {
+    url: url.toString(),
+    name,
+    value,
+    attributes
   })
+}
 
-  return res
+/**
+ * Returns the cookies that are sent along a request to `url`, except the `HttpOnly` ones.
+ *
+ * Requires the `cookies` feature of the plugin, the `http:allow-get-cookies` permission
+ * and `url` to be allowed by the scope.
+ *
+ * @since 2.6.0
+ */
+export async function getCookies(url: URL | string): Promise<Cookie[]> {
+  return await invoke('plugin:http|get_cookies', { url: url.toString() })
 }
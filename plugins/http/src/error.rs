@@ -31,18 +31,38 @@ pub enum Error {
     SchemeNotSupport(String),
     #[error("Request canceled")]
     RequestCanceled,
+    /// The request exceeds the rate limit configured for its host.
+    #[error("request to {0} exceeds its rate limit")]
+    RateLimited(String),
     #[error(transparent)]
     FsError(#[from] tauri_plugin_fs::Error),
+    /// File path not allowed by the fs scope.
+    #[error("path not allowed on the configured fs scope: {0}")]
+    PathNotAllowed(std::path::PathBuf),
+    /// A cookie set with the `set_cookie` command was rejected, e.g. for a domain not matching its URL.
+    #[error("invalid cookie: {0}")]
+    InvalidCookie(String),
+    #[error("multipart bodies used but the `multipart` feature is not enabled")]
+    MultipartNotEnabled,
+    /// A request was given both a body and multipart parts.
+    #[error("a request can't have both a body and multipart parts")]
+    ConflictingBody,
     #[error("failed to process data url")]
     DataUrlError,
     #[error("failed to decode data url into bytes")]
     DataUrlDecodeError,
+    #[error("response body exceeds the maximum size of {0} bytes")]
+    ResponseTooLarge(u64),
     #[error(transparent)]
     Tauri(#[from] tauri::Error),
     #[error(transparent)]
     Utf8(#[from] std::string::FromUtf8Error),
     #[error("dangerous settings used but are not enabled")]
     DangerousSettings,
+    #[error("invalid client identity: {0}")]
+    InvalidClientIdentity(String),
+    #[error("this client identity requires {0} feature to be enabled")]
+    ClientIdentityNotSupported(&'static str),
 }
 
 impl Serialize for Error {
@@ -6,7 +6,7 @@
 
 pub use reqwest;
 use tauri::{
-    plugin::{Builder, TauriPlugin},
+    plugin::{Builder as PluginBuilder, TauriPlugin},
     Manager, Runtime,
 };
 
@@ -14,6 +14,8 @@ pub use error::{Error, Result};
 
 mod commands;
 mod error;
+mod multipart;
+mod rate_limit;
 #[cfg(feature = "cookies")]
 mod reqwest_cookie_store;
 mod scope;
@@ -22,69 +24,131 @@ mod scope;
 const COOKIES_FILENAME: &str = ".cookies";
 
 pub(crate) struct Http {
+    clients: commands::ClientCache,
+    responses: commands::ResponseCache,
+    rate_limiter: std::sync::Arc<rate_limit::RateLimiter>,
     #[cfg(feature = "cookies")]
     cookies_jar: std::sync::Arc<crate::reqwest_cookie_store::CookieStoreMutex>,
 }
 
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    Builder::<R>::new("http")
-        .setup(|app, _| {
-            #[cfg(feature = "cookies")]
-            let cookies_jar = {
-                use crate::reqwest_cookie_store::*;
-                use std::fs::File;
-                use std::io::BufReader;
-
-                let cache_dir = app.path().app_cache_dir()?;
-                std::fs::create_dir_all(&cache_dir)?;
-
-                let path = cache_dir.join(COOKIES_FILENAME);
-                let file = File::options()
-                    .create(true)
-                    .append(true)
-                    .read(true)
-                    .open(&path)?;
-
-                let reader = BufReader::new(file);
-                CookieStoreMutex::load(path.clone(), reader).unwrap_or_else(|_e| {
-                    #[cfg(feature = "tracing")]
-                    tracing::warn!(
-                        "failed to load cookie store: {_e}, falling back to empty store"
-                    );
-                    CookieStoreMutex::new(path, Default::default())
-                })
-            };
-
-            let state = Http {
+    Builder::default().build()
+}
+
+/// The http plugin Builder, to configure the plugin before registering it.
+///
+/// ```no_run
+/// tauri::Builder::default()
+///     .plugin(
+///         tauri_plugin_http::Builder::new()
+///             .rate_limit("api.example.com", 10)
+///             .build(),
+///     );
+/// ```
+///
+/// [`init`] registers the plugin with the default configuration.
+#[derive(Default)]
+pub struct Builder {
+    rate_limits: Vec<(String, u32)>,
+    reject_rate_limited: bool,
+}
+
+impl Builder {
+    /// Create a new http plugin Builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the requests to the hosts matching `host_pattern` to `max_per_sec` per second.
+    ///
+    /// The pattern is either a host name like `api.example.com`, `*.example.com` to match
+    /// `example.com` and its subdomains, or `*` to match every host.
+    /// Each matching host gets its own budget, and the first matching pattern applies.
+    ///
+    /// Requests over the limit are delayed until they fit in it,
+    /// see [`Self::reject_rate_limited`] to reject them instead. Hosts are not limited by default.
+    pub fn rate_limit(mut self, host_pattern: impl Into<String>, max_per_sec: u32) -> Self {
+        self.rate_limits.push((host_pattern.into(), max_per_sec));
+        self
+    }
+
+    /// Fails requests over their host's rate limit with [`Error::RateLimited`] instead of delaying them.
+    pub fn reject_rate_limited(mut self, reject: bool) -> Self {
+        self.reject_rate_limited = reject;
+        self
+    }
+
+    /// Builds the plugin.
+    pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
+        let rate_limiter = rate_limit::RateLimiter::new(self.rate_limits, self.reject_rate_limited);
+        PluginBuilder::<R>::new("http")
+            .setup(move |app, _| {
                 #[cfg(feature = "cookies")]
-                cookies_jar: std::sync::Arc::new(cookies_jar),
-            };
+                let cookies_jar = {
+                    use crate::reqwest_cookie_store::*;
+                    use std::fs::File;
+                    use std::io::BufReader;
 
-            app.manage(state);
+                    let cache_dir = app.path().app_cache_dir()?;
+                    std::fs::create_dir_all(&cache_dir)?;
 
-            Ok(())
-        })
-        .on_event(|app, event| {
-            #[cfg(feature = "cookies")]
-            if let tauri::RunEvent::Exit = event {
-                let state = app.state::<Http>();
+                    let path = cache_dir.join(COOKIES_FILENAME);
+                    let file = File::options()
+                        .create(true)
+                        .append(true)
+                        .read(true)
+                        .open(&path)?;
 
-                match state.cookies_jar.request_save() {
-                    Ok(rx) => {
-                        let _ = rx.recv();
-                    }
-                    Err(_e) => {
+                    let reader = BufReader::new(file);
+                    CookieStoreMutex::load(path.clone(), reader).unwrap_or_else(|_e| {
                         #[cfg(feature = "tracing")]
-                        tracing::error!("failed to save cookie jar: {_e}");
+                        tracing::warn!(
+                            "failed to load cookie store: {_e}, falling back to empty store"
+                        );
+                        CookieStoreMutex::new(path, Default::default())
+                    })
+                };
+
+                let state = Http {
+                    clients: Default::default(),
+                    responses: Default::default(),
+                    rate_limiter: std::sync::Arc::new(rate_limiter),
+                    #[cfg(feature = "cookies")]
+                    cookies_jar: std::sync::Arc::new(cookies_jar),
+                };
+
+                app.manage(state);
+
+                Ok(())
+            })
+            .on_event(|app, event| {
+                #[cfg(feature = "cookies")]
+                if let tauri::RunEvent::Exit = event {
+                    let state = app.state::<Http>();
+
+                    match state.cookies_jar.request_save() {
+                        Ok(rx) => {
+                            let _ = rx.recv();
+                        }
+                        Err(_e) => {
+                            #[cfg(feature = "tracing")]
+                            tracing::error!("failed to save cookie jar: {_e}");
+                        }
                     }
                 }
-            }
-        })
-        .invoke_handler(tauri::generate_handler![
-            commands::fetch,
-            commands::fetch_cancel,
-            commands::fetch_send,
-            commands::fetch_read_body
-        ])
-        .build()
+            })
+            .invoke_handler(tauri::generate_handler![
+                commands::fetch,
+                commands::fetch_cancel,
+                commands::fetch_cancel_all,
+                #[cfg(feature = "cookies")]
+                commands::set_cookie,
+                #[cfg(feature = "cookies")]
+                commands::get_cookies,
+                commands::fetch_send,
+                commands::fetch_read_body,
+                commands::fetch_download
+            ])
+            .build()
+    }
 }
@@ -0,0 +1,279 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Streamed `multipart/form-data` request bodies.
+
+use serde::{Deserialize, Serialize};
+use tauri_plugin_fs::SafeFilePath;
+
+/// A part of a `multipart/form-data` request body.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)] // feature flags shouldn't affect the api
+pub struct MultipartPart {
+    name: String,
+    #[serde(flatten)]
+    body: PartBody,
+    file_name: Option<String>,
+    content_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+#[allow(dead_code)] // feature flags shouldn't affect the api
+enum PartBody {
+    /// Sent from memory.
+    Data { data: Vec<u8> },
+    /// Streamed from the file at `path`.
+    File { path: SafeFilePath },
+}
+
+/// Progress of a streamed request body, sent after each chunk.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)] // feature flags shouldn't affect the api
+pub struct UploadProgress {
+    sent: u64,
+    total: u64,
+}
+
+#[cfg(feature = "multipart")]
+pub(crate) use body::MultipartBody;
+
+#[cfg(feature = "multipart")]
+mod body {
+    use std::{
+        collections::hash_map::RandomState,
+        hash::{BuildHasher, Hasher},
+        path::{Path, PathBuf},
+    };
+
+    use futures_util::{stream, StreamExt, TryStreamExt};
+    use tauri::ipc::Channel;
+    use tokio_util::{bytes::Bytes, io::ReaderStream};
+
+    use super::{MultipartPart, PartBody, UploadProgress};
+    use crate::{Error, Result};
+
+    enum Segment {
+        Bytes(Bytes),
+        File(PathBuf),
+    }
+
+    /// The layout of a multipart body. Files are only opened once the body is sent.
+    pub(crate) struct MultipartBody {
+        boundary: String,
+        segments: Vec<Segment>,
+        len: u64,
+    }
+
+    impl MultipartBody {
+        /// Lays out `parts`, rejecting the file parts for which `is_allowed` returns `false`.
+        pub(crate) fn new(
+            parts: Vec<MultipartPart>,
+            is_allowed: impl Fn(&Path) -> bool,
+        ) -> Result<Self> {
+            let boundary = format!(
+                "tauri-plugin-http-{:016x}",
+                RandomState::new().build_hasher().finish()
+            );
+
+            let mut body = Self {
+                boundary,
+                segments: Vec::new(),
+                len: 0,
+            };
+
+            for part in parts {
+                let (content, content_len, file_name) = match part.body {
+                    PartBody::Data { data } => (Segment::Bytes(data.into()), None, part.file_name),
+                    PartBody::File { path } => {
+                        let path = path.into_path()?;
+                        if !is_allowed(&path) {
+                            return Err(Error::PathNotAllowed(path));
+                        }
+                        let len = std::fs::metadata(&path)?.len();
+                        let file_name = part.file_name.or_else(|| {
+                            path.file_name()
+                                .map(|name| name.to_string_lossy().into_owned())
+                        });
+                        (Segment::File(path), Some(len), file_name)
+                    }
+                };
+
+                let mut header = format!(
+                    "--{}\r\nContent-Disposition: form-data; name=\"{}\"",
+                    body.boundary,
+                    escape(&part.name)
+                );
+                if let Some(file_name) = &file_name {
+                    header.push_str(&format!("; filename=\"{}\"", escape(file_name)));
+                }
+                header.push_str("\r\n");
+                let content_type = part.content_type.or_else(|| {
+                    matches!(content, Segment::File(_))
+                        .then(|| "application/octet-stream".to_string())
+                });
+                if let Some(content_type) = content_type {
+                    header.push_str(&format!("Content-Type: {content_type}\r\n"));
+                }
+                header.push_str("\r\n");
+
+                body.push(Segment::Bytes(header.into()), None);
+                body.push(content, content_len);
+                body.push(Segment::Bytes(Bytes::from_static(b"\r\n")), None);
+            }
+
+            let closing = format!("--{}--\r\n", body.boundary);
+            body.push(Segment::Bytes(closing.into()), None);
+
+            Ok(body)
+        }
+
+        fn push(&mut self, segment: Segment, len: Option<u64>) {
+            self.len += match &segment {
+                Segment::Bytes(bytes) => bytes.len() as u64,
+                Segment::File(_) => len.unwrap_or_default(),
+            };
+            self.segments.push(segment);
+        }
+
+        pub(crate) fn content_type(&self) -> String {
+            format!("multipart/form-data; boundary={}", self.boundary)
+        }
+
+        pub(crate) fn len(&self) -> u64 {
+            self.len
+        }
+
+        /// Streams the body, reading the files in chunks and reporting the bytes sent to `on_progress`.
+        pub(crate) fn into_body(
+            self,
+            on_progress: Option<Channel<UploadProgress>>,
+        ) -> reqwest::Body {
+            let total = self.len;
+            let mut sent = 0;
+            let stream = stream::iter(self.segments)
+                .flat_map(|segment| match segment {
+                    Segment::Bytes(bytes) => stream::once(async move { Ok(bytes) }).boxed(),
+                    Segment::File(path) => stream::once(tokio::fs::File::open(path))
+                        .map_ok(ReaderStream::new)
+                        .try_flatten()
+                        .boxed(),
+                })
+                .inspect_ok(move |chunk| {
+                    sent += chunk.len() as u64;
+                    if let Some(on_progress) = &on_progress {
+                        let _ = on_progress.send(UploadProgress { sent, total });
+                    }
+                });
+            reqwest::Body::wrap_stream(stream)
+        }
+    }
+
+    /// Percent-encodes the characters that would end a quoted header parameter,
+    /// like browsers do for form data.
+    fn escape(value: &str) -> String {
+        value
+            .replace('"', "%22")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    }
+}
+
+#[cfg(all(test, feature = "multipart"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tauri::ipc::{Channel, InvokeResponseBody};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn streams_file_and_memory_parts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("video.bin");
+        let file = (0..200_000u32).map(|i| i as u8).collect::<Vec<_>>();
+        std::fs::write(&path, &file).unwrap();
+
+        let parts: Vec<MultipartPart> = serde_json::from_value(serde_json::json!([
+            {
+                "name": "metadata",
+                "data": br#"{"title":"video"}"#.to_vec(),
+                "contentType": "application/json"
+            },
+            { "name": "file", "path": path }
+        ]))
+        .unwrap();
+
+        let body = MultipartBody::new(parts, |p| p == path).unwrap();
+        let (content_type, len) = (body.content_type(), body.len());
+
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let progress_ = progress.clone();
+        let on_progress = Channel::new(move |msg: InvokeResponseBody| -> tauri::Result<()> {
+            let InvokeResponseBody::Json(json) = msg else {
+                unreachable!()
+            };
+            let progress: serde_json::Value = serde_json::from_str(&json).unwrap();
+            progress_.lock().unwrap().push((
+                progress["sent"].as_u64().unwrap(),
+                progress["total"].as_u64().unwrap(),
+            ));
+            Ok(())
+        });
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .match_header("content-type", content_type.as_str())
+            .match_header("content-length", len.to_string().as_str())
+            .with_body_from_request(|request| request.body().unwrap().clone())
+            .create_async()
+            .await;
+
+        let received = reqwest::Client::new()
+            .post(server.url())
+            .header(http::header::CONTENT_TYPE, &content_type)
+            .header(http::header::CONTENT_LENGTH, len)
+            .body(body.into_body(Some(on_progress)))
+            .send()
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        mock.assert_async().await;
+        assert_eq!(received.len() as u64, len);
+
+        let boundary = content_type.split_once("boundary=").unwrap().1;
+        let mut expected = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"metadata\"\r\n\
+             Content-Type: application/json\r\n\r\n{{\"title\":\"video\"}}\r\n\
+             --{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"video.bin\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n"
+        )
+        .into_bytes();
+        expected.extend_from_slice(&file);
+        expected.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        assert!(received == expected);
+
+        let progress = progress.lock().unwrap();
+        assert!(progress.len() > 2);
+        assert!(progress.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(*progress.last().unwrap(), (len, len));
+    }
+
+    #[test]
+    fn rejects_files_outside_of_the_scope() {
+        let parts: Vec<MultipartPart> =
+            serde_json::from_value(serde_json::json!([{ "name": "file", "path": "/etc/passwd" }]))
+                .unwrap();
+
+        assert!(matches!(
+            MultipartBody::new(parts, |_| false),
+            Err(crate::Error::PathNotAllowed(_))
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{Error, Result};
+
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// A maximum number of requests per second to the hosts matching `host_pattern`.
+///
+/// The pattern is either a host name, `*.` followed by a domain to match the domain and its
+/// subdomains, or `*` to match every host.
+#[derive(Debug, Clone)]
+struct RateLimit {
+    host_pattern: String,
+    max_per_sec: u32,
+}
+
+impl RateLimit {
+    fn matches(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+        let pattern = self.host_pattern.to_ascii_lowercase();
+        match pattern.strip_prefix("*.") {
+            Some(domain) => {
+                host == domain
+                    || host
+                        .strip_suffix(domain)
+                        .is_some_and(|subdomain| subdomain.ends_with('.'))
+            }
+            None => pattern == "*" || host == pattern,
+        }
+    }
+}
+
+/// Throttles requests per host over a sliding window of one second.
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    limits: Vec<RateLimit>,
+    reject: bool,
+    /// Times at which the requests of the last second were (or will be) sent, by host.
+    sent: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limits: Vec<(String, u32)>, reject: bool) -> Self {
+        Self {
+            limits: limits
+                .into_iter()
+                .map(|(host_pattern, max_per_sec)| RateLimit {
+                    host_pattern,
+                    // a limit of zero would never let a request through
+                    max_per_sec: max_per_sec.max(1),
+                })
+                .collect(),
+            reject,
+            sent: Default::default(),
+        }
+    }
+
+    /// Waits until a request to `host` fits in its rate limit,
+    /// or fails with [`Error::RateLimited`] if the limiter rejects requests instead.
+    pub(crate) async fn acquire(&self, host: &str) -> Result<()> {
+        let now = Instant::now();
+        let delay = self.reserve(host, now)?;
+        if !delay.is_zero() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("delaying request to {host} by {delay:?} to respect its rate limit");
+            // the slot is given back if the request is dropped while waiting for it
+            let reservation = Reservation {
+                limiter: self,
+                host,
+                at: now + delay,
+            };
+            tokio::time::sleep(delay).await;
+            std::mem::forget(reservation);
+        }
+        Ok(())
+    }
+
+    /// Reserves a slot for a request to `host` made at `now`, returning how long to wait for it.
+    fn reserve(&self, host: &str, now: Instant) -> Result<Duration> {
+        // the first matching limit applies
+        let Some(limit) = self.limits.iter().find(|limit| limit.matches(host)) else {
+            return Ok(Duration::ZERO);
+        };
+        let max = limit.max_per_sec as usize;
+
+        let mut sent = self.sent.lock().unwrap();
+        // forget the hosts that had no request in the last second
+        sent.retain(|_, sent| {
+            while sent.front().is_some_and(|at| *at + WINDOW <= now) {
+                sent.pop_front();
+            }
+            !sent.is_empty()
+        });
+        let sent = sent.entry(host.to_ascii_lowercase()).or_default();
+
+        // the request is sent once the window no longer holds `max` requests
+        let at = match sent.len().checked_sub(max) {
+            Some(i) => (sent[i] + WINDOW).max(now),
+            None => now,
+        };
+        if at > now && self.reject {
+            return Err(Error::RateLimited(host.into()));
+        }
+        sent.push_back(at);
+        Ok(at - now)
+    }
+
+    /// Frees the slot reserved at `at` for a request to `host` that won't be sent.
+    fn release(&self, host: &str, at: Instant) {
+        let host = host.to_ascii_lowercase();
+        let mut sent = self.sent.lock().unwrap();
+        if let Some(times) = sent.get_mut(&host) {
+            if let Some(i) = times.iter().rposition(|time| *time == at) {
+                times.remove(i);
+            }
+            if times.is_empty() {
+                sent.remove(&host);
+            }
+        }
+    }
+}
+
+/// A slot reserved by [`RateLimiter::acquire`], released when dropped.
+struct Reservation<'a> {
+    limiter: &'a RateLimiter,
+    host: &'a str,
+    at: Instant,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        self.limiter.release(self.host, self.at);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_host_patterns() {
+        let limit = |host_pattern: &str| RateLimit {
+            host_pattern: host_pattern.into(),
+            max_per_sec: 1,
+        };
+
+        assert!(limit("*").matches("tauri.app"));
+        assert!(limit("api.tauri.app").matches("API.tauri.app"));
+        assert!(!limit("api.tauri.app").matches("tauri.app"));
+        assert!(limit("*.tauri.app").matches("tauri.app"));
+        assert!(limit("*.tauri.app").matches("v2.api.tauri.app"));
+        assert!(!limit("*.tauri.app").matches("nottauri.app"));
+    }
+
+    #[test]
+    fn throttles_bursts() {
+        let limiter = RateLimiter::new(vec![("api.tauri.app".into(), 2)], false);
+        let now = Instant::now();
+
+        let delays: Vec<_> = (0..5)
+            .map(|_| limiter.reserve("api.tauri.app", now).unwrap())
+            .collect();
+        assert_eq!(
+            delays,
+            [Duration::ZERO, Duration::ZERO, WINDOW, WINDOW, WINDOW * 2]
+        );
+
+        // the slots free up as the window slides
+        let later = now + WINDOW * 3;
+        assert_eq!(
+            limiter.reserve("api.tauri.app", later).unwrap(),
+            Duration::ZERO
+        );
+
+        // other hosts are not limited
+        for _ in 0..5 {
+            assert_eq!(limiter.reserve("tauri.app", now).unwrap(), Duration::ZERO);
+        }
+    }
+
+    #[test]
+    fn limits_hosts_independently() {
+        let limiter = RateLimiter::new(vec![("*.tauri.app".into(), 1)], false);
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve("a.tauri.app", now).unwrap(), Duration::ZERO);
+        assert_eq!(limiter.reserve("b.tauri.app", now).unwrap(), Duration::ZERO);
+        assert_eq!(limiter.reserve("a.tauri.app", now).unwrap(), WINDOW);
+    }
+
+    #[test]
+    fn rejects_bursts() {
+        let limiter = RateLimiter::new(vec![("*".into(), 2)], true);
+        let now = Instant::now();
+
+        assert!(limiter.reserve("tauri.app", now).is_ok());
+        assert!(limiter.reserve("tauri.app", now).is_ok());
+        assert!(matches!(
+            limiter.reserve("tauri.app", now),
+            Err(Error::RateLimited(host)) if host == "tauri.app"
+        ));
+        // rejected requests don't take a slot
+        assert!(limiter.reserve("tauri.app", now + WINDOW).is_ok());
+    }
+
+    #[test]
+    fn forgets_idle_hosts() {
+        let limiter = RateLimiter::new(vec![("*".into(), 1)], false);
+        let now = Instant::now();
+
+        limiter.reserve("a.tauri.app", now).unwrap();
+        limiter.reserve("b.tauri.app", now).unwrap();
+        limiter.reserve("c.tauri.app", now + WINDOW).unwrap();
+        assert_eq!(
+            limiter.sent.lock().unwrap().keys().collect::<Vec<_>>(),
+            ["c.tauri.app"]
+        );
+    }
+
+    #[tokio::test]
+    async fn releases_the_slots_of_dropped_requests() {
+        let limiter = RateLimiter::new(vec![("tauri.app".into(), 1)], false);
+
+        limiter.acquire("tauri.app").await.unwrap();
+        // the second request waits for the next window and is dropped while waiting
+        let waiting =
+            tokio::time::timeout(Duration::from_millis(10), limiter.acquire("tauri.app")).await;
+        assert!(waiting.is_err());
+
+        // so the next request takes its slot instead of waiting for the window after
+        let delay = limiter.reserve("tauri.app", Instant::now()).unwrap();
+        assert!(delay <= WINDOW);
+        assert_eq!(limiter.sent.lock().unwrap()["tauri.app"].len(), 2);
+    }
+}
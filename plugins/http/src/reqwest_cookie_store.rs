@@ -9,8 +9,9 @@ use std::{
     sync::{mpsc::Receiver, Mutex},
 };
 
-use cookie_store::{CookieStore, RawCookie, RawCookieParseError};
+use cookie_store::{CookieError, CookieStore, RawCookie, RawCookieParseError};
 use reqwest::header::HeaderValue;
+use serde::{Deserialize, Serialize};
 
 fn set_cookies(
     cookie_store: &mut CookieStore,
@@ -104,6 +105,89 @@ impl CookieStoreMutex {
             .replace(CancellableTask(task));
         Ok(rx)
     }
+
+    /// Stores `cookie` as if a response from `url` had set it, then persists the store.
+    pub fn insert(&self, cookie: &RawCookie<'_>, url: &url::Url) -> Result<(), CookieError> {
+        self.store.lock().unwrap().insert_raw(cookie, url)?;
+
+        if let Err(_e) = self.request_save() {
+            #[cfg(feature = "tracing")]
+            tracing::error!("failed to save cookie jar: {_e}");
+        }
+        Ok(())
+    }
+
+    /// The cookies sent along a request to `url`, except the `HttpOnly` ones.
+    pub fn get(&self, url: &url::Url) -> Vec<Cookie> {
+        self.store
+            .lock()
+            .unwrap()
+            .matches(url)
+            .into_iter()
+            .filter(|cookie| cookie.http_only() != Some(true))
+            .map(|cookie| Cookie {
+                name: cookie.name().into(),
+                value: cookie.value().into(),
+            })
+            .collect()
+    }
+}
+
+/// A cookie sent along requests, see [`CookieStoreMutex::get`].
+#[derive(Debug, Serialize)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+}
+
+/// The attributes of a cookie set with the `set_cookie` command, like those of a `Set-Cookie` header.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CookieAttributes {
+    /// Also send the cookie to the subdomains of this domain, instead of the host of the URL only.
+    domain: Option<String>,
+    path: Option<String>,
+    /// Number of seconds until the cookie expires, it is not persisted if unset.
+    max_age: Option<i64>,
+    #[serde(default)]
+    secure: bool,
+    #[serde(default)]
+    http_only: bool,
+    same_site: Option<SameSite>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl CookieAttributes {
+    /// Builds the cookie `name` with these attributes.
+    pub fn build(self, name: String, value: String) -> RawCookie<'static> {
+        let mut cookie = RawCookie::new(name, value);
+        if let Some(domain) = self.domain {
+            cookie.set_domain(domain);
+        }
+        if let Some(path) = self.path {
+            cookie.set_path(path);
+        }
+        if let Some(max_age) = self.max_age {
+            cookie.set_max_age(cookie::time::Duration::seconds(max_age));
+        }
+        cookie.set_secure(self.secure);
+        cookie.set_http_only(self.http_only);
+        if let Some(same_site) = self.same_site {
+            cookie.set_same_site(match same_site {
+                SameSite::Strict => cookie::SameSite::Strict,
+                SameSite::Lax => cookie::SameSite::Lax,
+                SameSite::None => cookie::SameSite::None,
+            });
+        }
+        cookie
+    }
 }
 
 impl reqwest::cookie::CookieStore for CookieStoreMutex {
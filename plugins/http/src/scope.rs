@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use serde::{Deserialize, Deserializer};
 use url::Url;
@@ -11,7 +11,11 @@ use urlpattern::{UrlPattern, UrlPatternMatchInput};
 #[allow(rustdoc::bare_urls)]
 #[derive(Debug)]
 pub struct Entry {
-    pub url: UrlPattern,
+    /// `None` for the entries scoping the files sent in multipart bodies.
+    pub url: Option<UrlPattern>,
+    /// A file that can be sent in multipart bodies, glob patterns and path variables are supported.
+    #[cfg_attr(not(feature = "multipart"), allow(dead_code))]
+    pub path: Option<PathBuf>,
 }
 
 fn parse_url_pattern(s: &str) -> Result<UrlPattern, urlpattern::quirks::Error> {
@@ -38,6 +42,7 @@ fn parse_url_pattern(s: &str) -> Result<UrlPattern, urlpattern::quirks::Error> {
 pub(crate) enum EntryRaw {
     Value(String),
     Object { url: String },
+    Path { path: PathBuf },
 }
 
 impl<'de> Deserialize<'de> for Entry {
@@ -49,11 +54,18 @@ impl<'de> Deserialize<'de> for Entry {
             let url = match raw {
                 EntryRaw::Value(url) => url,
                 EntryRaw::Object { url } => url,
+                EntryRaw::Path { path } => {
+                    return Ok(Entry {
+                        url: None,
+                        path: Some(path),
+                    })
+                }
             };
             Ok(Entry {
-                url: parse_url_pattern(&url).map_err(|e| {
+                url: Some(parse_url_pattern(&url).map_err(|e| {
                     serde::de::Error::custom(format!("`{url}` is not a valid URL pattern: {e}"))
-                })?,
+                })?),
+                path: None,
             })
         })
     }
@@ -75,19 +87,21 @@ impl<'a> Scope<'a> {
     /// Determines if the given URL is allowed on this scope.
     pub fn is_allowed(&self, url: &Url) -> bool {
         let denied = self.denied.iter().any(|entry| {
-            entry
-                .url
-                .test(UrlPatternMatchInput::Url(url.clone()))
-                .unwrap_or_default()
+            entry.url.as_ref().is_some_and(|pattern| {
+                pattern
+                    .test(UrlPatternMatchInput::Url(url.clone()))
+                    .unwrap_or_default()
+            })
         });
         if denied {
             false
         } else {
             self.allowed.iter().any(|entry| {
-                entry
-                    .url
-                    .test(UrlPatternMatchInput::Url(url.clone()))
-                    .unwrap_or_default()
+                entry.url.as_ref().is_some_and(|pattern| {
+                    pattern
+                        .test(UrlPatternMatchInput::Url(url.clone()))
+                        .unwrap_or_default()
+                })
             })
         }
     }
@@ -104,7 +118,10 @@ mod tests {
 
         fn from_str(s: &str) -> Result<Self, Self::Err> {
             let pattern = super::parse_url_pattern(s)?;
-            Ok(Self { url: pattern })
+            Ok(Self {
+                url: Some(pattern),
+                path: None,
+            })
         }
     }
 
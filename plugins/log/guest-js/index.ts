@@ -8,7 +8,23 @@ import { listen, type UnlistenFn, type Event } from '@tauri-apps/api/event'
 export interface LogOptions {
   file?: string
   line?: number
-  keyValues?: Record<string, string | undefined>
+  /**
+   * Structured fields attached to the record. Values that are not strings are logged as JSON.
+   */
+  keyValues?: Record<string, unknown>
+  /**
+   * The target of the record, logged as `webview:<target>` so it can be filtered like Rust targets.
+   * Defaults to the caller location.
+   *
+   * @since 2.8.0
+   */
+  target?: string
+  /**
+   * The module the record originates from.
+   *
+   * @since 2.8.0
+   */
+  module?: string
 }
 
 export enum LogLevel {
@@ -117,7 +133,7 @@ async function log(
 ): Promise<void> {
   const location = getCallerLocation(new Error().stack)
 
-  const { file, line, keyValues } = options ?? {}
+  const { file, line, keyValues, target, module } = options ?? {}
 
   await invoke('plugin:log|log', {
     level,
@@ -125,7 +141,9 @@ async function log(
     location,
     file,
     line,
-    keyValues
+    keyValues,
+    target,
+    module
   })
 }
 
@@ -243,6 +261,12 @@ export async function trace(
 interface RecordPayload {
   level: LogLevel
   message: string
+  /**
+   * The key-value pairs attached to the log entry.
+   *
+   * @since 2.8.0
+   */
+  keyValues?: Record<string, string>
 }
 
 type LoggerFn = (fn: RecordPayload) => void
@@ -255,7 +279,7 @@ type LoggerFn = (fn: RecordPayload) => void
  */
 export async function attachLogger(fn: LoggerFn): Promise<UnlistenFn> {
   return await listen('log://log', (event: Event<RecordPayload>) => {
-    const { level } = event.payload
+    const { level, keyValues } = event.payload
     let { message } = event.payload
 
     // Strip ANSI escape codes
@@ -265,7 +289,7 @@ export async function attachLogger(fn: LoggerFn): Promise<UnlistenFn> {
       /[\u001b\u009b][[()#;?]*(?:[0-9]{1,4}(?:;[0-9]{0,4})*)?[0-9A-ORZcf-nqry=><]/g,
       ''
     )
-    fn({ message, level })
+    fn({ message, level, keyValues })
   })
 }
 
@@ -8,36 +8,89 @@ use log::RecordBuilder;
 
 use crate::{LogLevel, WEBVIEW_TARGET};
 
+/// A log record sent by the frontend.
+struct WebviewRecord<'a> {
+    level: LogLevel,
+    message: String,
+    location: Option<&'a str>,
+    file: Option<&'a str>,
+    line: Option<u32>,
+    key_values: HashMap<String, serde_json::Value>,
+    target: Option<&'a str>,
+    module: Option<&'a str>,
+}
+
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub fn log(
     level: LogLevel,
     message: String,
     location: Option<&str>,
     file: Option<&str>,
     line: Option<u32>,
-    key_values: Option<HashMap<String, String>>,
+    key_values: Option<HashMap<String, serde_json::Value>>,
+    target: Option<&str>,
+    module: Option<&str>,
 ) {
-    let level = log::Level::from(level);
+    log_record(
+        log::logger(),
+        WebviewRecord {
+            level,
+            message,
+            location,
+            file,
+            line,
+            key_values: key_values.unwrap_or_default(),
+            target,
+            module,
+        },
+    );
+}
 
-    let target = if let Some(location) = location {
-        format!("{WEBVIEW_TARGET}:{location}")
-    } else {
-        WEBVIEW_TARGET.to_string()
+/// Logs a frontend record to `logger`, under the [`WEBVIEW_TARGET`] target
+/// followed by the explicit target, or the caller location if there is none.
+fn log_record(logger: &dyn log::Log, record: WebviewRecord<'_>) {
+    let level = log::Level::from(record.level);
+
+    let target = match record.target.or(record.location) {
+        Some(target) => format!("{WEBVIEW_TARGET}:{target}"),
+        None => WEBVIEW_TARGET.to_string(),
     };
 
     let mut builder = RecordBuilder::new();
-    builder.level(level).target(&target).file(file).line(line);
+    builder
+        .level(level)
+        .target(&target)
+        .module_path(record.module)
+        .file(record.file)
+        .line(record.line);
 
-    let key_values = key_values.unwrap_or_default();
+    // structured values are logged as their JSON representation, strings as is
+    let values: Vec<(&str, String)> = record
+        .key_values
+        .iter()
+        .map(|(k, v)| match v {
+            serde_json::Value::String(v) => (k.as_str(), v.clone()),
+            v => (k.as_str(), v.to_string()),
+        })
+        .collect();
     let mut kv = HashMap::new();
-    for (k, v) in key_values.iter() {
-        kv.insert(k.as_str(), v.as_str());
+    for (k, v) in values.iter() {
+        kv.insert(*k, v.as_str());
     }
     builder.key_values(&kv);
     #[cfg(feature = "tracing")]
-    emit_trace(level, &message, location, file, line, &kv);
+    emit_trace(
+        level,
+        &record.message,
+        record.location,
+        record.file,
+        record.line,
+        &kv,
+    );
 
-    log::logger().log(&builder.args(format_args!("{message}")).build());
+    let message = record.message;
+    logger.log(&builder.args(format_args!("{message}")).build());
 }
 
 // Target becomes default and location is added as a parameter
@@ -71,3 +124,87 @@ fn emit_trace(
         log::Level::Trace => emit_event!(tracing::Level::TRACE),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, sync::Arc};
+
+    use log::LevelFilter;
+    use time::OffsetDateTime;
+
+    use super::*;
+    use crate::{get_log_file_path, KeyValues, RotationStrategy, DEFAULT_MAX_FILE_SIZE};
+
+    #[test]
+    fn frontend_records_reach_the_file_target() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+
+        let clock: crate::Clock = Arc::new(OffsetDateTime::now_utc);
+        let path = get_log_file_path(
+            &dir,
+            "webview",
+            &RotationStrategy::KeepOne,
+            &clock,
+            DEFAULT_MAX_FILE_SIZE,
+            None,
+        )
+        .unwrap();
+        let (_, logger) = fern::Dispatch::new()
+            .level(LevelFilter::Info)
+            .filter(|metadata| metadata.target().starts_with(WEBVIEW_TARGET))
+            .format(|out, message, record| {
+                out.finish(format_args!(
+                    "[{}][{}][{}] {message}{}",
+                    record.target(),
+                    record.module_path().unwrap_or_default(),
+                    record.level(),
+                    KeyValues(record.key_values())
+                ))
+            })
+            .chain(fern::log_file(&path).unwrap())
+            .into_log();
+
+        log_record(
+            &*logger,
+            WebviewRecord {
+                level: LogLevel::Info,
+                message: "payment accepted".into(),
+                location: Some("pay@checkout.js:10:5"),
+                file: None,
+                line: None,
+                key_values: serde_json::from_value(
+                    serde_json::json!({ "amount": 42, "currency": "EUR" }),
+                )
+                .unwrap(),
+                target: Some("payments"),
+                module: Some("checkout"),
+            },
+        );
+        log_record(
+            &*logger,
+            WebviewRecord {
+                level: LogLevel::Debug,
+                message: "filtered out".into(),
+                location: None,
+                file: None,
+                line: None,
+                key_values: HashMap::new(),
+                target: None,
+                module: None,
+            },
+        );
+        logger.flush();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let (line, kv) = contents
+            .trim_end()
+            .split_once(" payment accepted ")
+            .unwrap();
+        assert_eq!(line, "[webview:payments][checkout][INFO]");
+        let mut kv: Vec<_> = kv.split(' ').collect();
+        kv.sort();
+        assert_eq!(kv, ["amount=42", "currency=EUR"]);
+        assert!(!contents.contains("filtered out"));
+    }
+}
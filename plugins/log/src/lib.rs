@@ -15,10 +15,17 @@ use serde::Serialize;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::borrow::Cow;
 use std::{
-    fmt::Arguments,
+    collections::BTreeMap,
+    fmt::{self, Arguments, Display},
     fs::{self, File},
+    io::{BufWriter, Write},
     iter::FromIterator,
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, RwLock, Weak,
+    },
+    time::{Duration, Instant},
 };
 use tauri::{
     plugin::{self, TauriPlugin},
@@ -142,10 +149,76 @@ impl TimezoneStrategy {
     }
 }
 
+/// A source of the current time, used for log timestamps and rotated file names.
+pub type Clock = Arc<dyn Fn() -> OffsetDateTime + Send + Sync>;
+
+type SizeThresholdFn = Arc<dyn Fn(&Path, u128) + Send + Sync>;
+
 #[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 struct RecordPayload {
     message: String,
     level: LogLevel,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    key_values: BTreeMap<String, String>,
+}
+
+impl From<&Record<'_>> for RecordPayload {
+    fn from(record: &Record<'_>) -> Self {
+        struct Collect(BTreeMap<String, String>);
+
+        impl<'kvs> log::kv::VisitSource<'kvs> for Collect {
+            fn visit_pair(
+                &mut self,
+                key: log::kv::Key<'kvs>,
+                value: log::kv::Value<'kvs>,
+            ) -> Result<(), log::kv::Error> {
+                self.0.insert(key.to_string(), value.to_string());
+                Ok(())
+            }
+        }
+
+        let mut key_values = Collect(BTreeMap::new());
+        let _ = record.key_values().visit(&mut key_values);
+
+        Self {
+            message: record.args().to_string(),
+            level: record.level().into(),
+            key_values: key_values.0,
+        }
+    }
+}
+
+/// Displays the key-value pairs attached to a record as ` key=value` pairs.
+///
+/// The default formats append them to the message, use this to do the same in a custom [`Builder::format`]:
+///
+/// ```rust
+/// use tauri_plugin_log::KeyValues;
+/// tauri_plugin_log::Builder::new()
+///     .format(|out, message, record| {
+///         out.finish(format_args!("[{}] {message}{}", record.level(), KeyValues(record.key_values())))
+///     });
+/// ```
+pub struct KeyValues<'a>(pub &'a dyn log::kv::Source);
+
+impl Display for KeyValues<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Write<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+        impl<'kvs> log::kv::VisitSource<'kvs> for Write<'_, '_> {
+            fn visit_pair(
+                &mut self,
+                key: log::kv::Key<'kvs>,
+                value: log::kv::Value<'kvs>,
+            ) -> Result<(), log::kv::Error> {
+                write!(self.0, " {key}={value}")?;
+                Ok(())
+            }
+        }
+
+        self.0.visit(&mut Write(f)).map_err(|_| fmt::Error)
+    }
 }
 
 /// An enum representing the available targets of the logger.
@@ -175,6 +248,9 @@ pub enum TargetKind {
     /// Forward logs to the webview (via the `log://log` event).
     ///
     /// This requires the webview to subscribe to log events, via this plugins `attachConsole` function.
+    ///
+    /// Records logged by the frontend itself, under the [`WEBVIEW_TARGET`] target, are never forwarded
+    /// back to the webview to prevent feedback loops. They still reach the other targets.
     Webview,
     /// Send logs to a [`fern::Dispatch`]
     ///
@@ -186,6 +262,7 @@ pub enum TargetKind {
 pub struct Target {
     kind: TargetKind,
     filters: Vec<Box<Filter>>,
+    level_route: Option<LevelFilter>,
 }
 
 impl Target {
@@ -194,6 +271,7 @@ impl Target {
         Self {
             kind,
             filters: Vec::new(),
+            level_route: None,
         }
     }
 
@@ -211,8 +289,12 @@ pub struct Builder {
     dispatch: fern::Dispatch,
     rotation_strategy: RotationStrategy,
     timezone_strategy: TimezoneStrategy,
+    clock: Option<Clock>,
     max_file_size: u128,
+    size_threshold: Option<(u128, SizeThresholdFn)>,
+    unbuffered: bool,
     targets: Vec<Target>,
+    dedup: Option<Duration>,
     is_skip_logger: bool,
 }
 
@@ -223,14 +305,20 @@ impl Default for Builder {
         let dispatch = fern::Dispatch::new().format(move |out, message, record| {
             out.finish(
                 #[cfg(mobile)]
-                format_args!("[{}] {}", record.target(), message),
+                format_args!(
+                    "[{}] {}{}",
+                    record.target(),
+                    message,
+                    KeyValues(record.key_values())
+                ),
                 #[cfg(desktop)]
                 format_args!(
-                    "{}[{}][{}] {}",
+                    "{}[{}][{}] {}{}",
                     DEFAULT_TIMEZONE_STRATEGY.get_now().format(&format).unwrap(),
                     record.target(),
                     record.level(),
-                    message
+                    message,
+                    KeyValues(record.key_values())
                 ),
             )
         });
@@ -238,8 +326,12 @@ impl Default for Builder {
             dispatch,
             rotation_strategy: DEFAULT_ROTATION_STRATEGY,
             timezone_strategy: DEFAULT_TIMEZONE_STRATEGY,
+            clock: None,
             max_file_size: DEFAULT_MAX_FILE_SIZE,
+            size_threshold: None,
+            unbuffered: false,
             targets: DEFAULT_LOG_TARGETS.into(),
+            dedup: None,
             is_skip_logger: false,
         }
     }
@@ -256,16 +348,46 @@ impl Builder {
     }
 
     pub fn timezone_strategy(mut self, timezone_strategy: TimezoneStrategy) -> Self {
-        self.timezone_strategy = timezone_strategy.clone();
+        self.timezone_strategy = timezone_strategy;
+        self.with_clock_format()
+    }
+
+    /// Sets the clock used for the log timestamps and the dated names of rotated log files.
+    ///
+    /// Defaults to the current time in the configured [`TimezoneStrategy`].
+    /// A fixed clock makes the output deterministic, which is mostly useful in tests.
+    ///
+    /// Like [`Self::timezone_strategy`], this replaces the formatter.
+    pub fn with_clock<F>(mut self, clock: F) -> Self
+    where
+        F: Fn() -> OffsetDateTime + Send + Sync + 'static,
+    {
+        self.clock = Some(Arc::new(clock));
+        self.with_clock_format()
+    }
+
+    fn clock(&self) -> Clock {
+        match &self.clock {
+            Some(clock) => clock.clone(),
+            None => {
+                let timezone_strategy = self.timezone_strategy.clone();
+                Arc::new(move || timezone_strategy.get_now())
+            }
+        }
+    }
+
+    fn with_clock_format(mut self) -> Self {
+        let clock = self.clock();
 
         let format = format_description!("[[[year]-[month]-[day]][[[hour]:[minute]:[second]]");
         self.dispatch = self.dispatch.format(move |out, message, record| {
             out.finish(format_args!(
-                "{}[{}][{}] {}",
-                timezone_strategy.get_now().format(&format).unwrap(),
+                "{}[{}][{}] {}{}",
+                clock().format(&format).unwrap(),
                 record.level(),
                 record.target(),
-                message
+                message,
+                KeyValues(record.key_values())
             ))
         });
         self
@@ -276,6 +398,29 @@ impl Builder {
         self
     }
 
+    /// Calls `callback` with the path and size of a log file larger than `bytes` when it's opened,
+    /// e.g. to warn that the logs are getting large when they are cleaned up externally.
+    ///
+    /// This is independent of the [`RotationStrategy`]: a file also larger than the
+    /// [maximum file size](Self::max_file_size) is reported before being rotated.
+    pub fn on_size_threshold<F>(mut self, bytes: u128, callback: F) -> Self
+    where
+        F: Fn(&Path, u128) + Send + Sync + 'static,
+    {
+        self.size_threshold = Some((bytes, Arc::new(callback)));
+        self
+    }
+
+    /// Whether the file targets sync every record to disk as soon as it's written,
+    /// so the last records survive a system crash or power loss. Defaults to `false`.
+    ///
+    /// The records are always handed to the operating system once written, so they already survive
+    /// a crash of the app itself. Syncing them to disk trades logging throughput for crash safety.
+    pub fn unbuffered(mut self, unbuffered: bool) -> Self {
+        self.unbuffered = unbuffered;
+        self
+    }
+
     pub fn format<F>(mut self, formatter: F) -> Self
     where
         F: Fn(FormatCallback, &Arguments, &Record) + Sync + Send + 'static,
@@ -320,6 +465,44 @@ impl Builder {
         self
     }
 
+    /// Adds a log target that only receives records routed to the given level.
+    ///
+    /// A routed target receives every record at `level` or more severe, except the ones
+    /// claimed by another routed target with a stricter level. Targets added with
+    /// [`Self::target`] are not affected by the routing.
+    ///
+    /// ```rust
+    /// use tauri_plugin_log::{log::LevelFilter, Target, TargetKind};
+    /// tauri_plugin_log::Builder::new()
+    ///     .clear_targets()
+    ///     // errors only
+    ///     .route_level(LevelFilter::Error, Target::new(TargetKind::LogDir { file_name: Some("error".into()) }))
+    ///     // everything else
+    ///     .route_level(LevelFilter::Trace, Target::new(TargetKind::LogDir { file_name: Some("app".into()) }));
+    /// ```
+    pub fn route_level(mut self, level: LevelFilter, mut target: Target) -> Self {
+        target.level_route.replace(level);
+        self.targets.push(target);
+        self
+    }
+
+    /// Collapses identical consecutive records logged within `window` of the first one.
+    ///
+    /// Records are identical when their level, target and message match. The first record is
+    /// logged right away, and the suppressed repetitions are summarized by a single
+    /// `<message> (repeated N times)` record once the window closes or a different record is logged.
+    /// The deduplication happens before the records are dispatched, so it applies to all targets.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// tauri_plugin_log::Builder::new()
+    ///     .dedup(Duration::from_secs(1));
+    /// ```
+    pub fn dedup(mut self, window: Duration) -> Self {
+        self.dedup.replace(window);
+        self
+    }
+
     /// Skip the creation and global registration of a logger
     ///
     /// If you wish to use your own global logger, you must call `skip_logger` so that the plugin does not attempt to set a second global logger. In this configuration, no logger will be created and the plugin's `log` command will rely on the result of `log::logger()`. You will be responsible for configuring the logger yourself and any included targets will be ignored. If ever initializing the plugin multiple times, such as if registering the plugin while testing, call this method to avoid panicking when registering multiple loggers. For interacting with `tracing`, you can leverage the `tracing-log` logger to forward logs to `tracing` or enable the `tracing` feature for this plugin to emit events directly to the tracing system. Both scenarios require calling this method.
@@ -356,112 +539,48 @@ impl Builder {
     pub fn with_colors(self, colors: fern::colors::ColoredLevelConfig) -> Self {
         let format = format_description!("[[[year]-[month]-[day]][[[hour]:[minute]:[second]]");
 
-        let timezone_strategy = self.timezone_strategy.clone();
+        let clock = self.clock();
         self.format(move |out, message, record| {
             out.finish(format_args!(
-                "{}[{}][{}] {}",
-                timezone_strategy.get_now().format(&format).unwrap(),
+                "{}[{}][{}] {}{}",
+                clock().format(&format).unwrap(),
                 colors.color(record.level()),
                 record.target(),
-                message
+                message,
+                KeyValues(record.key_values())
             ))
         })
     }
 
+    #[allow(clippy::type_complexity)]
     fn acquire_logger<R: Runtime>(
         app_handle: &AppHandle<R>,
         mut dispatch: fern::Dispatch,
-        rotation_strategy: RotationStrategy,
-        timezone_strategy: TimezoneStrategy,
-        max_file_size: u128,
+        files: FileOptions,
         targets: Vec<Target>,
-    ) -> Result<(log::LevelFilter, Box<dyn log::Log>), Error> {
-        let app_name = &app_handle.package_info().name;
+        dedup: Option<Duration>,
+    ) -> Result<(log::LevelFilter, Box<dyn log::Log>, Arc<DynamicTargets>), Error> {
+        let routed_levels = targets
+            .iter()
+            .filter_map(|target| target.level_route)
+            .collect::<Vec<_>>();
 
         // setup targets
         for target in targets {
-            let mut target_dispatch = fern::Dispatch::new();
-            for filter in target.filters {
-                target_dispatch = target_dispatch.filter(filter);
-            }
-
-            let logger = match target.kind {
-                #[cfg(target_os = "android")]
-                TargetKind::Stdout | TargetKind::Stderr => fern::Output::call(android_logger::log),
-                #[cfg(target_os = "ios")]
-                TargetKind::Stdout | TargetKind::Stderr => fern::Output::call(move |record| {
-                    let message = format!("{}", record.args());
-                    unsafe {
-                        ios::tauri_log(
-                            match record.level() {
-                                log::Level::Trace | log::Level::Debug => 1,
-                                log::Level::Info => 2,
-                                log::Level::Warn | log::Level::Error => 3,
-                            },
-                            // The string is allocated in rust, so we must
-                            // autorelease it rust to give it to the Swift
-                            // runtime.
-                            objc2::rc::Retained::autorelease_ptr(
-                                objc2_foundation::NSString::from_str(message.as_str()),
-                            ) as _,
-                        );
-                    }
-                }),
-                #[cfg(desktop)]
-                TargetKind::Stdout => std::io::stdout().into(),
-                #[cfg(desktop)]
-                TargetKind::Stderr => std::io::stderr().into(),
-                TargetKind::Folder { path, file_name } => {
-                    if !path.exists() {
-                        fs::create_dir_all(&path)?;
-                    }
-
-                    fern::log_file(get_log_file_path(
-                        &path,
-                        file_name.as_deref().unwrap_or(app_name),
-                        &rotation_strategy,
-                        &timezone_strategy,
-                        max_file_size,
-                    )?)?
-                    .into()
-                }
-                TargetKind::LogDir { file_name } => {
-                    let path = app_handle.path().app_log_dir()?;
-                    if !path.exists() {
-                        fs::create_dir_all(&path)?;
-                    }
-
-                    fern::log_file(get_log_file_path(
-                        &path,
-                        file_name.as_deref().unwrap_or(app_name),
-                        &rotation_strategy,
-                        &timezone_strategy,
-                        max_file_size,
-                    )?)?
-                    .into()
-                }
-                TargetKind::Webview => {
-                    let app_handle = app_handle.clone();
-
-                    fern::Output::call(move |record| {
-                        let payload = RecordPayload {
-                            message: record.args().to_string(),
-                            level: record.level().into(),
-                        };
-                        let app_handle = app_handle.clone();
-                        tauri::async_runtime::spawn(async move {
-                            let _ = app_handle.emit("log://log", payload);
-                        });
-                    })
-                }
-                TargetKind::Dispatch(dispatch) => dispatch.into(),
-            };
-            target_dispatch = target_dispatch.chain(logger);
-
-            dispatch = dispatch.chain(target_dispatch);
+            dispatch = dispatch.chain(target_dispatch(app_handle, target, &routed_levels, &files)?);
         }
 
-        Ok(dispatch.into_log())
+        // the targets added with `LogExt::add_target` are reached through this logger
+        let dynamic_targets = Arc::new(DynamicTargets::new(files));
+        dispatch = dispatch
+            .chain(Box::new(DynamicTargetsLogger(dynamic_targets.clone())) as Box<dyn log::Log>);
+
+        let (max_level, log) = dispatch.into_log();
+        let log = match dedup {
+            Some(window) => Box::new(DedupLogger::new(log, window)),
+            None => log,
+        };
+        Ok((max_level, log, dynamic_targets))
     }
 
     fn plugin_builder<R: Runtime>() -> plugin::Builder<R> {
@@ -477,31 +596,46 @@ impl Builder {
             return Err(Error::LoggerNotInitialized);
         }
         let plugin = Self::plugin_builder();
-        let (max_level, log) = Self::acquire_logger(
-            app_handle,
-            self.dispatch,
-            self.rotation_strategy,
-            self.timezone_strategy,
-            self.max_file_size,
-            self.targets,
-        )?;
+        let files = FileOptions {
+            clock: self.clock(),
+            rotation_strategy: self.rotation_strategy,
+            max_file_size: self.max_file_size,
+            size_threshold: self.size_threshold,
+            unbuffered: self.unbuffered,
+        };
+        let (max_level, log, dynamic_targets) =
+            Self::acquire_logger(app_handle, self.dispatch, files, self.targets, self.dedup)?;
 
-        Ok((plugin.build(), max_level, log))
+        let plugin = plugin
+            .setup(move |app_handle, _api| {
+                app_handle.manage(dynamic_targets);
+                Ok(())
+            })
+            .build();
+
+        Ok((plugin, max_level, log))
     }
 
     pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
         Self::plugin_builder()
             .setup(move |app_handle, _api| {
                 if !self.is_skip_logger {
-                    let (max_level, log) = Self::acquire_logger(
+                    let files = FileOptions {
+                        clock: self.clock(),
+                        rotation_strategy: self.rotation_strategy,
+                        max_file_size: self.max_file_size,
+                        size_threshold: self.size_threshold,
+                        unbuffered: self.unbuffered,
+                    };
+                    let (max_level, log, dynamic_targets) = Self::acquire_logger(
                         app_handle,
                         self.dispatch,
-                        self.rotation_strategy,
-                        self.timezone_strategy,
-                        self.max_file_size,
+                        files,
                         self.targets,
+                        self.dedup,
                     )?;
                     attach_logger(max_level, log)?;
+                    app_handle.manage(dynamic_targets);
                 }
                 Ok(())
             })
@@ -509,6 +643,227 @@ impl Builder {
     }
 }
 
+/// Builds the dispatch of `target`, leaving out the records routed to the other `routed_levels`.
+fn target_dispatch<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    target: Target,
+    routed_levels: &[LevelFilter],
+    files: &FileOptions,
+) -> Result<fern::Dispatch, Error> {
+    let app_name = &app_handle.package_info().name;
+
+    let mut target_dispatch = fern::Dispatch::new();
+    if let Some(level) = target.level_route {
+        target_dispatch = target_dispatch.filter(level_route_filter(level, routed_levels));
+    }
+    for filter in target.filters {
+        target_dispatch = target_dispatch.filter(filter);
+    }
+    // the frontend may log the records it receives, which would send them back to it endlessly
+    if matches!(target.kind, TargetKind::Webview) {
+        target_dispatch = target_dispatch.filter(|metadata| !is_webview_record(metadata));
+    }
+
+    let logger = match target.kind {
+        #[cfg(target_os = "android")]
+        TargetKind::Stdout | TargetKind::Stderr => fern::Output::call(android_logger::log),
+        #[cfg(target_os = "ios")]
+        TargetKind::Stdout | TargetKind::Stderr => fern::Output::call(move |record| {
+            let message = format!("{}", record.args());
+            unsafe {
+                ios::tauri_log(
+                    match record.level() {
+                        log::Level::Trace | log::Level::Debug => 1,
+                        log::Level::Info => 2,
+                        log::Level::Warn | log::Level::Error => 3,
+                    },
+                    // The string is allocated in rust, so we must
+                    // autorelease it rust to give it to the Swift
+                    // runtime.
+                    objc2::rc::Retained::autorelease_ptr(objc2_foundation::NSString::from_str(
+                        message.as_str(),
+                    )) as _,
+                );
+            }
+        }),
+        #[cfg(desktop)]
+        TargetKind::Stdout => std::io::stdout().into(),
+        #[cfg(desktop)]
+        TargetKind::Stderr => std::io::stderr().into(),
+        TargetKind::Folder { path, file_name } => {
+            files.output(&path, file_name.as_deref().unwrap_or(app_name))?
+        }
+        TargetKind::LogDir { file_name } => files.output(
+            &app_handle.path().app_log_dir()?,
+            file_name.as_deref().unwrap_or(app_name),
+        )?,
+        TargetKind::Webview => {
+            let app_handle = app_handle.clone();
+
+            fern::Output::call(move |record| {
+                let payload = RecordPayload::from(record);
+                let app_handle = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    let _ = app_handle.emit("log://log", payload);
+                });
+            })
+        }
+        TargetKind::Dispatch(dispatch) => dispatch.into(),
+    };
+    Ok(target_dispatch.chain(logger))
+}
+
+/// Whether the record was logged by the frontend, under the [`WEBVIEW_TARGET`] target.
+fn is_webview_record(metadata: &log::Metadata) -> bool {
+    metadata
+        .target()
+        .strip_prefix(WEBVIEW_TARGET)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(':'))
+}
+
+/// How the file targets are written.
+struct FileOptions {
+    rotation_strategy: RotationStrategy,
+    clock: Clock,
+    max_file_size: u128,
+    size_threshold: Option<(u128, SizeThresholdFn)>,
+    unbuffered: bool,
+}
+
+impl FileOptions {
+    /// Opens the `<file_name>.log` file in `dir`, rotating the existing one first if it's too large.
+    fn output(&self, dir: &Path, file_name: &str) -> Result<fern::Output, Error> {
+        if !dir.exists() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let file = fern::log_file(get_log_file_path(
+            &dir,
+            file_name,
+            &self.rotation_strategy,
+            &self.clock,
+            self.max_file_size,
+            self.size_threshold.as_ref(),
+        )?)?;
+        Ok(if self.unbuffered {
+            fern::Output::writer(Box::new(SyncedFile(BufWriter::new(file))), "\n")
+        } else {
+            file.into()
+        })
+    }
+}
+
+/// A file that can be synced to disk, see [`SyncedFile`].
+trait SyncData {
+    fn sync_data(&self) -> std::io::Result<()>;
+}
+
+impl SyncData for File {
+    fn sync_data(&self) -> std::io::Result<()> {
+        File::sync_data(self)
+    }
+}
+
+/// A log file synced to disk whenever it's flushed, which fern does after every record.
+struct SyncedFile<F: Write>(BufWriter<F>);
+
+impl<F: Write + SyncData> Write for SyncedFile<F> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()?;
+        self.0.get_ref().sync_data()
+    }
+}
+
+/// Identifies a target added with [`LogExt::add_target`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TargetId(u64);
+
+/// The targets added after the logger was attached, see [`LogExt`].
+struct DynamicTargets {
+    files: FileOptions,
+    next_id: AtomicU64,
+    loggers: RwLock<Vec<(TargetId, Box<dyn log::Log>)>>,
+}
+
+impl DynamicTargets {
+    fn new(files: FileOptions) -> Self {
+        Self {
+            files,
+            next_id: AtomicU64::new(0),
+            loggers: Default::default(),
+        }
+    }
+
+    fn add(&self, logger: Box<dyn log::Log>) -> TargetId {
+        let id = TargetId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.loggers.write().unwrap().push((id, logger));
+        id
+    }
+
+    fn remove(&self, id: TargetId) -> bool {
+        let mut loggers = self.loggers.write().unwrap();
+        let Some(index) = loggers.iter().position(|(target, _)| *target == id) else {
+            return false;
+        };
+        let (_, logger) = loggers.remove(index);
+        logger.flush();
+        true
+    }
+}
+
+/// Forwards the records to the targets added at runtime.
+struct DynamicTargetsLogger(Arc<DynamicTargets>);
+
+impl log::Log for DynamicTargetsLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        let loggers = self.0.loggers.read().unwrap();
+        loggers.iter().any(|(_, logger)| logger.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record) {
+        for (_, logger) in self.0.loggers.read().unwrap().iter() {
+            logger.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        for (_, logger) in self.0.loggers.read().unwrap().iter() {
+            logger.flush();
+        }
+    }
+}
+
+/// Extensions to add and remove log targets once the logger is attached.
+///
+/// The records sent to the added targets go through the format and level of the [`Builder`].
+pub trait LogExt<R: Runtime> {
+    /// Starts sending the records to `target` until it is removed with [`LogExt::remove_target`].
+    fn add_target(&self, target: Target) -> Result<TargetId, Error>;
+
+    /// Stops sending the records to the target, returns `false` if it was already removed.
+    fn remove_target(&self, id: TargetId) -> bool;
+}
+
+impl<R: Runtime, T: Manager<R>> LogExt<R> for T {
+    fn add_target(&self, target: Target) -> Result<TargetId, Error> {
+        let targets = self
+            .try_state::<Arc<DynamicTargets>>()
+            .ok_or(Error::LoggerNotInitialized)?;
+        let (_, logger) =
+            target_dispatch(self.app_handle(), target, &[], &targets.files)?.into_log();
+        Ok(targets.add(logger))
+    }
+
+    fn remove_target(&self, id: TargetId) -> bool {
+        self.try_state::<Arc<DynamicTargets>>()
+            .is_some_and(|targets| targets.remove(id))
+    }
+}
+
 /// Attaches the given logger
 pub fn attach_logger(
     max_level: log::LevelFilter,
@@ -519,17 +874,152 @@ pub fn attach_logger(
     Ok(())
 }
 
+/// Wraps a logger to collapse identical consecutive records, see [`Builder::dedup`].
+struct DedupLogger(Arc<Dedup>);
+
+struct Dedup {
+    logger: Box<dyn log::Log>,
+    window: Duration,
+    last: Mutex<Option<LastRecord>>,
+}
+
+struct LastRecord {
+    level: log::Level,
+    target: String,
+    message: String,
+    logged_at: Instant,
+    repeated: usize,
+}
+
+impl LastRecord {
+    fn log_repeated(&self, logger: &dyn log::Log) {
+        if self.repeated > 0 {
+            logger.log(
+                &Record::builder()
+                    .level(self.level)
+                    .target(&self.target)
+                    .args(format_args!(
+                        "{} (repeated {} times)",
+                        self.message, self.repeated
+                    ))
+                    .build(),
+            );
+        }
+    }
+}
+
+impl DedupLogger {
+    fn new(logger: Box<dyn log::Log>, window: Duration) -> Self {
+        let dedup = Arc::new(Dedup {
+            logger,
+            window,
+            last: Default::default(),
+        });
+
+        // summarize the repetitions even if nothing else is logged after them
+        let weak = Arc::downgrade(&dedup);
+        std::thread::spawn(move || loop {
+            std::thread::sleep(window);
+            let Some(dedup) = Weak::upgrade(&weak) else {
+                break;
+            };
+            dedup.take_last(|last| last.logged_at.elapsed() >= dedup.window);
+        });
+
+        Self(dedup)
+    }
+}
+
+impl Dedup {
+    /// Clears the last record if `predicate` matches, logging its repetitions.
+    fn take_last(&self, predicate: impl FnOnce(&LastRecord) -> bool) {
+        let mut last = self.last.lock().unwrap();
+        let taken = if last
+            .as_ref()
+            .is_some_and(|last| last.repeated > 0 && predicate(last))
+        {
+            last.take()
+        } else {
+            None
+        };
+        // the inner logger may be slow or log itself, it's called without holding the lock
+        drop(last);
+
+        if let Some(taken) = taken {
+            taken.log_repeated(&*self.logger);
+        }
+    }
+}
+
+impl log::Log for DedupLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.0.logger.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let message = record.args().to_string();
+        let mut last = self.0.last.lock().unwrap();
+        if let Some(last) = last.as_mut().filter(|last| {
+            last.level == record.level()
+                && last.target == record.target()
+                && last.message == message
+                && last.logged_at.elapsed() < self.0.window
+        }) {
+            last.repeated += 1;
+            return;
+        }
+
+        let previous = last.replace(LastRecord {
+            level: record.level(),
+            target: record.target().to_string(),
+            message,
+            logged_at: Instant::now(),
+            repeated: 0,
+        });
+        drop(last);
+
+        if let Some(previous) = previous {
+            previous.log_repeated(&*self.0.logger);
+        }
+        self.0.logger.log(record);
+    }
+
+    fn flush(&self) {
+        self.0.take_last(|_| true);
+        self.0.logger.flush();
+    }
+}
+
+/// Builds the filter of a target routed to `level`, leaving out the records
+/// claimed by the routes with a stricter level.
+fn level_route_filter(
+    level: LevelFilter,
+    routed_levels: &[LevelFilter],
+) -> impl Fn(&log::Metadata) -> bool + Send + Sync + 'static {
+    let stricter = routed_levels
+        .iter()
+        .copied()
+        .filter(|routed| *routed < level)
+        .max();
+    move |metadata| {
+        metadata.level() <= level && stricter.map_or(true, |stricter| metadata.level() > stricter)
+    }
+}
+
 fn rename_file_to_dated(
     path: &impl AsRef<Path>,
     dir: &impl AsRef<Path>,
     file_name: &str,
-    timezone_strategy: &TimezoneStrategy,
+    clock: &Clock,
 ) -> Result<(), Error> {
     let to = dir.as_ref().join(format!(
         "{}_{}.log",
         file_name,
-        timezone_strategy
-            .get_now()
+        clock()
             .format(&time::format_description::parse(LOG_DATE_FORMAT).unwrap())
             .unwrap(),
     ));
@@ -551,17 +1041,23 @@ fn get_log_file_path(
     dir: &impl AsRef<Path>,
     file_name: &str,
     rotation_strategy: &RotationStrategy,
-    timezone_strategy: &TimezoneStrategy,
+    clock: &Clock,
     max_file_size: u128,
+    size_threshold: Option<&(u128, SizeThresholdFn)>,
 ) -> Result<PathBuf, Error> {
     let path = dir.as_ref().join(format!("{file_name}.log"));
 
     if path.exists() {
         let log_size = File::open(&path)?.metadata()?.len() as u128;
+        if let Some((threshold, callback)) = size_threshold {
+            if log_size > *threshold {
+                callback(&path, log_size);
+            }
+        }
         if log_size > max_file_size {
             match rotation_strategy {
                 RotationStrategy::KeepAll => {
-                    rename_file_to_dated(&path, dir, file_name, timezone_strategy)?;
+                    rename_file_to_dated(&path, dir, file_name, clock)?;
                 }
                 RotationStrategy::KeepSome(how_many) => {
                     let mut files = fs::read_dir(dir)?
@@ -591,7 +1087,7 @@ fn get_log_file_path(
                             fs::remove_file(old_log_path)?;
                         }
                     }
-                    rename_file_to_dated(&path, dir, file_name, timezone_strategy)?;
+                    rename_file_to_dated(&path, dir, file_name, clock)?;
                 }
                 RotationStrategy::KeepOne => {
                     fs::remove_file(&path)?;
@@ -601,3 +1097,336 @@ fn get_log_file_path(
     }
     Ok(path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capture(records: Arc<Mutex<Vec<String>>>) -> fern::Output {
+        fern::Output::call(move |record| records.lock().unwrap().push(record.args().to_string()))
+    }
+
+    #[test]
+    fn routes_error_records_only_to_the_error_target() {
+        let routed_levels = [LevelFilter::Error, LevelFilter::Trace];
+        let errors: Arc<Mutex<Vec<String>>> = Default::default();
+        let others: Arc<Mutex<Vec<String>>> = Default::default();
+
+        let app = tauri::test::mock_app();
+        let files = FileOptions {
+            rotation_strategy: RotationStrategy::KeepOne,
+            clock: Arc::new(OffsetDateTime::now_utc),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            size_threshold: None,
+            unbuffered: false,
+        };
+        let routed = |level, records: &Arc<Mutex<Vec<String>>>| {
+            let mut target = Target::new(TargetKind::Dispatch(
+                fern::Dispatch::new().chain(capture(records.clone())),
+            ));
+            target.level_route = Some(level);
+            target_dispatch(app.handle(), target, &routed_levels, &files).unwrap()
+        };
+
+        let (_, logger) = fern::Dispatch::new()
+            .chain(routed(LevelFilter::Error, &errors))
+            .chain(routed(LevelFilter::Trace, &others))
+            .into_log();
+
+        logger.log(
+            &Record::builder()
+                .level(log::Level::Error)
+                .args(format_args!("failure"))
+                .build(),
+        );
+        logger.log(
+            &Record::builder()
+                .level(log::Level::Info)
+                .args(format_args!("started"))
+                .build(),
+        );
+
+        assert_eq!(*errors.lock().unwrap(), ["failure"]);
+        assert_eq!(*others.lock().unwrap(), ["started"]);
+    }
+
+    #[test]
+    fn webview_records_do_not_bounce_back_to_the_webview() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let (tx, rx) = std::sync::mpsc::channel();
+        app.listen_any("log://log", move |event| {
+            let payload: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+            let _ = tx.send(payload["message"].as_str().unwrap().to_string());
+        });
+        let file: Arc<Mutex<Vec<String>>> = Default::default();
+
+        let files = FileOptions {
+            rotation_strategy: RotationStrategy::KeepOne,
+            clock: Arc::new(OffsetDateTime::now_utc),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            size_threshold: None,
+            unbuffered: false,
+        };
+        let webview = Target::new(TargetKind::Webview);
+        let dispatch = Target::new(TargetKind::Dispatch(
+            fern::Dispatch::new().chain(capture(file.clone())),
+        ));
+        let (_, logger) = fern::Dispatch::new()
+            .chain(target_dispatch(app.handle(), webview, &[], &files).unwrap())
+            .chain(target_dispatch(app.handle(), dispatch, &[], &files).unwrap())
+            .into_log();
+
+        for target in ["webview", "webview:payments", "app::updater", "webviews"] {
+            logger.log(
+                &Record::builder()
+                    .level(log::Level::Info)
+                    .target(target)
+                    .args(format_args!("{target}"))
+                    .build(),
+            );
+        }
+
+        // the records are emitted from spawned tasks, in any order
+        let mut forwarded = (0..2)
+            .map(|_| rx.recv_timeout(Duration::from_secs(5)).unwrap())
+            .collect::<Vec<_>>();
+        forwarded.sort();
+        assert_eq!(forwarded, ["app::updater", "webviews"]);
+        assert!(rx.recv_timeout(Duration::from_millis(200)).is_err());
+        assert_eq!(
+            *file.lock().unwrap(),
+            ["webview", "webview:payments", "app::updater", "webviews"]
+        );
+    }
+
+    #[test]
+    fn key_values_are_included_in_the_payload_and_text() {
+        let key_values = [("request_id", "42"), ("user", "alice")];
+        let record = Record::builder()
+            .level(log::Level::Info)
+            .key_values(&key_values)
+            .args(format_args!("request handled"))
+            .build();
+
+        let payload = serde_json::to_value(RecordPayload::from(&record)).unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "message": "request handled",
+                "level": 3,
+                "keyValues": { "request_id": "42", "user": "alice" }
+            })
+        );
+
+        assert_eq!(
+            KeyValues(record.key_values()).to_string(),
+            " request_id=42 user=alice"
+        );
+    }
+
+    #[test]
+    fn collapses_identical_consecutive_records() {
+        let records: Arc<Mutex<Vec<String>>> = Default::default();
+        let (_, logger) = fern::Dispatch::new()
+            .chain(capture(records.clone()))
+            .into_log();
+        let logger = DedupLogger::new(logger, Duration::from_secs(60));
+
+        let log = |target: &str, message: &str| {
+            log::Log::log(
+                &logger,
+                &Record::builder()
+                    .level(log::Level::Warn)
+                    .target(target)
+                    .args(format_args!("{message}"))
+                    .build(),
+            )
+        };
+        for _ in 0..1000 {
+            log("app", "retrying");
+        }
+        log("other", "retrying");
+        log("app", "connected");
+        log("app", "connected");
+        log::Log::flush(&logger);
+
+        assert_eq!(
+            *records.lock().unwrap(),
+            [
+                "retrying",
+                "retrying (repeated 999 times)",
+                "retrying",
+                "connected",
+                "connected (repeated 1 times)"
+            ]
+        );
+    }
+
+    #[test]
+    fn summarizes_repetitions_once_the_window_closes() {
+        let records: Arc<Mutex<Vec<String>>> = Default::default();
+        let (_, logger) = fern::Dispatch::new()
+            .chain(capture(records.clone()))
+            .into_log();
+        let logger = DedupLogger::new(logger, Duration::from_millis(20));
+
+        for _ in 0..3 {
+            log::Log::log(
+                &logger,
+                &Record::builder()
+                    .level(log::Level::Info)
+                    .args(format_args!("tick"))
+                    .build(),
+            );
+        }
+        std::thread::sleep(Duration::from_millis(200));
+
+        assert_eq!(
+            *records.lock().unwrap(),
+            ["tick", "tick (repeated 2 times)"]
+        );
+    }
+
+    #[test]
+    fn records_stop_reaching_a_removed_target() {
+        let targets = Arc::new(DynamicTargets::new(FileOptions {
+            rotation_strategy: RotationStrategy::KeepOne,
+            clock: Arc::new(OffsetDateTime::now_utc),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            size_threshold: None,
+            unbuffered: false,
+        }));
+        let (_, logger) = fern::Dispatch::new()
+            .chain(Box::new(DynamicTargetsLogger(targets.clone())) as Box<dyn log::Log>)
+            .into_log();
+        let log = |message: &str| {
+            logger.log(
+                &Record::builder()
+                    .level(log::Level::Info)
+                    .args(format_args!("{message}"))
+                    .build(),
+            )
+        };
+
+        log("before");
+        let records: Arc<Mutex<Vec<String>>> = Default::default();
+        let (_, target) = fern::Dispatch::new()
+            .chain(capture(records.clone()))
+            .into_log();
+        let id = targets.add(target);
+        log("while added");
+        assert!(targets.remove(id));
+        log("after");
+
+        assert_eq!(*records.lock().unwrap(), ["while added"]);
+        assert!(!targets.remove(id));
+    }
+
+    /// Records what was written to it and how much of it was synced.
+    #[derive(Clone, Default)]
+    struct FakeFile {
+        written: Arc<Mutex<Vec<u8>>>,
+        synced: Arc<Mutex<usize>>,
+    }
+
+    impl Write for FakeFile {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.written.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SyncData for FakeFile {
+        fn sync_data(&self) -> std::io::Result<()> {
+            *self.synced.lock().unwrap() = self.written.lock().unwrap().len();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn unbuffered_records_are_synced_once_logged() {
+        let file = FakeFile::default();
+        let (_, logger) = fern::Dispatch::new()
+            .chain(fern::Output::writer(
+                Box::new(SyncedFile(BufWriter::new(file.clone()))),
+                "\n",
+            ))
+            .into_log();
+
+        for message in ["first", "about to crash"] {
+            logger.log(
+                &Record::builder()
+                    .level(log::Level::Error)
+                    .args(format_args!("{message}"))
+                    .build(),
+            );
+            // synced while the logger is alive and was never flushed
+            let written = file.written.lock().unwrap().len();
+            assert_eq!(*file.synced.lock().unwrap(), written);
+        }
+        assert_eq!(*file.written.lock().unwrap(), b"first\nabout to crash\n");
+    }
+
+    #[test]
+    fn rotated_file_is_named_after_the_clock() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("app.log"), "too large").unwrap();
+
+        let clock: Clock = Arc::new(|| time::macros::datetime!(2024-03-05 07:08:09 UTC));
+        let path =
+            get_log_file_path(&dir, "app", &RotationStrategy::KeepAll, &clock, 1, None).unwrap();
+
+        assert_eq!(path, dir.join("app.log"));
+        assert!(!path.exists());
+        let rotated = dir.join("app_2024-03-05_07-08-09.log");
+        assert_eq!(fs::read_to_string(rotated).unwrap(), "too large");
+    }
+
+    #[test]
+    fn size_threshold_fires_once_crossed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+
+        let reported: Arc<Mutex<Vec<(PathBuf, u128)>>> = Default::default();
+        let reported_ = reported.clone();
+        let callback: SizeThresholdFn = Arc::new(move |path: &Path, size| {
+            reported_.lock().unwrap().push((path.to_path_buf(), size))
+        });
+        let threshold = (10, callback);
+        let clock: Clock = Arc::new(|| time::macros::datetime!(2024-03-05 07:08:09 UTC));
+        let open = |max_file_size| {
+            get_log_file_path(
+                &dir,
+                "app",
+                &RotationStrategy::KeepAll,
+                &clock,
+                max_file_size,
+                Some(&threshold),
+            )
+            .unwrap()
+        };
+
+        fs::write(dir.join("app.log"), "small").unwrap();
+        open(DEFAULT_MAX_FILE_SIZE);
+        assert!(reported.lock().unwrap().is_empty());
+
+        fs::write(dir.join("app.log"), "larger than ten bytes").unwrap();
+        let path = open(DEFAULT_MAX_FILE_SIZE);
+        assert_eq!(*reported.lock().unwrap(), [(path.clone(), 21)]);
+        // not rotated, the threshold only reports the size
+        assert!(path.exists());
+
+        // a file over the maximum size is still rotated after being reported
+        open(15);
+        assert_eq!(reported.lock().unwrap().len(), 2);
+        assert!(!path.exists());
+        assert!(dir.join("app_2024-03-05_07-08-09.log").exists());
+    }
+}
@@ -37,6 +37,9 @@ import { invoke } from '@tauri-apps/api/core'
  * @param openWith The app to open the URL with. If not specified, defaults to the system default application for the specified url type.
  * On mobile, `openWith` can be provided as `inAppBrowser` to open the URL in an in-app browser. Otherwise, it will open the URL in the system default browser.
  *
+ * The URL must start with a scheme and have control characters, whitespace and quotes percent-encoded,
+ * otherwise it is rejected.
+ *
  * @since 2.0.0
  */
 export async function openUrl(
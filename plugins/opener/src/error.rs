@@ -20,10 +20,16 @@ pub enum Error {
     Json(#[from] serde_json::Error),
     #[error("unknown program {0}")]
     UnknownProgramName(String),
+    #[error("no application found for identifier {0}")]
+    UnknownAppId(String),
+    #[error("no application found to open {0}")]
+    NoHandler(String),
     #[error("Not allowed to open path {}{}", .path, .with.as_ref().map(|w| format!(" with {w}")).unwrap_or_default())]
     ForbiddenPath { path: String, with: Option<String> },
     #[error("Not allowed to open url {}{}", .url, .with.as_ref().map(|w| format!(" with {w}")).unwrap_or_default())]
     ForbiddenUrl { url: String, with: Option<String> },
+    #[error("Invalid url {url}: {reason}")]
+    InvalidUrl { url: String, reason: &'static str },
     #[error("API not supported on the current platform")]
     UnsupportedPlatform,
     #[error(transparent)]
@@ -0,0 +1,424 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Resolves the application that opens a target, without opening it.
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+/// An application that handles a target, see [`would_open`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppInfo {
+    /// The display name of the application.
+    pub name: String,
+    /// The location of the application.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **macOS**: The `.app` bundle, or the executable when `with` is a program.
+    /// - **Windows**: The executable.
+    /// - **Linux**: The desktop entry of the default application, or the executable when `with` is a program.
+    pub path: PathBuf,
+}
+
+/// What a target is opened as.
+enum Target<'a> {
+    /// A URL, handled by the application registered for its scheme.
+    Url(&'a str),
+    /// A file or directory, handled by the application registered for its type.
+    Path(&'a Path),
+}
+
+#[cfg(not(target_os = "macos"))]
+fn scheme(url: &str) -> &str {
+    url.split_once(':').map_or(url, |(scheme, _)| scheme)
+}
+
+/// Resolves the application that [`open_url`](crate::open_url) or [`open_path`](crate::open_path)
+/// would launch for `target`, without launching it.
+///
+/// `target` is a URL if it starts with a scheme, a path otherwise. When `with` is `None`, this is
+/// the default application for the URL scheme or the file type, otherwise the application `with`
+/// refers to. Fails with [`Error::NoHandler`](crate::Error::NoHandler) if there is none.
+///
+/// ## Platform-specific:
+///
+/// - **Linux**: The default application is queried with `xdg-mime`.
+/// - **Android / iOS**: Unsupported.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     let browser = tauri_plugin_opener::would_open("https://tauri.app", None)?;
+///     println!("links open in {}", browser.name);
+///     Ok(())
+///   });
+/// ```
+pub fn would_open(target: &str, with: Option<&str>) -> crate::Result<AppInfo> {
+    let no_handler = || crate::Error::NoHandler(target.into());
+
+    if let Some(with) = with {
+        return imp::find_app(with)
+            .or_else(|| find_program(with).map(app_info))
+            .ok_or_else(no_handler);
+    }
+
+    let target = match crate::open::validate_url(target) {
+        Ok(()) => Target::Url(target),
+        Err(_) => {
+            let path = Path::new(target);
+            // Returns an IO error if not exists, and besides `exists()` is a shorthand for `metadata()`
+            _ = path.metadata()?;
+            Target::Path(path)
+        }
+    };
+    imp::default_handler(&target).ok_or_else(no_handler)
+}
+
+/// Looks `program` up like the system does when launching it: as a path, or by name in `PATH`.
+fn find_program(program: &str) -> Option<PathBuf> {
+    let path = Path::new(program);
+    if path.components().count() > 1 {
+        return program_file(path.to_path_buf());
+    }
+
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .find_map(|dir| program_file(dir.join(program)))
+}
+
+/// `path` if it is a file, or on Windows `path` with one of the `PATHEXT` extensions.
+fn program_file(path: PathBuf) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path);
+    }
+
+    if cfg!(windows) && path.extension().is_none() {
+        let extensions = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into());
+        return extensions.split(';').find_map(|extension| {
+            let mut file = OsString::from(&path);
+            file.push(extension);
+            Some(PathBuf::from(file)).filter(|file| file.is_file())
+        });
+    }
+
+    None
+}
+
+fn app_info(path: PathBuf) -> AppInfo {
+    AppInfo {
+        name: path
+            .file_stem()
+            .unwrap_or(path.as_os_str())
+            .to_string_lossy()
+            .into_owned(),
+        path,
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::path::PathBuf;
+
+    use objc2_app_kit::NSWorkspace;
+    use objc2_foundation::{NSString, NSURL};
+
+    use super::{app_info, AppInfo, Target};
+
+    /// Finds the application bundle `with` refers to, as `open -a` does.
+    pub fn find_app(with: &str) -> Option<AppInfo> {
+        let path = PathBuf::from(with);
+        if path.extension().is_some_and(|extension| extension == "app") && path.is_dir() {
+            return Some(app_info(path));
+        }
+
+        let bundle = format!("{with}.app");
+        let home = std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Applications"));
+        [
+            "/Applications",
+            "/Applications/Utilities",
+            "/System/Applications",
+            "/System/Applications/Utilities",
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .chain(home)
+        .map(|dir| dir.join(&bundle))
+        .find(|path| path.is_dir())
+        .map(app_info)
+    }
+
+    pub fn default_handler(target: &Target) -> Option<AppInfo> {
+        unsafe {
+            let url = match target {
+                Target::Url(url) => NSURL::URLWithString(&NSString::from_str(url))?,
+                Target::Path(path) => {
+                    NSURL::fileURLWithPath(&NSString::from_str(&path.to_string_lossy()))
+                }
+            };
+            let app = NSWorkspace::sharedWorkspace().URLForApplicationToOpenURL(&url)?;
+            let path = app.path()?;
+            Some(app_info(PathBuf::from(path.to_string())))
+        }
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::path::PathBuf;
+
+    use windows::{
+        core::{w, HSTRING, PCWSTR, PWSTR},
+        Win32::{
+            System::Registry::{
+                RegGetValueW, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, RRF_RT_REG_SZ,
+            },
+            UI::Shell::{
+                AssocQueryStringW, ASSOCF_INIT_IGNOREUNKNOWN, ASSOCSTR, ASSOCSTR_EXECUTABLE,
+                ASSOCSTR_FRIENDLYAPPNAME,
+            },
+        },
+    };
+
+    use super::{app_info, AppInfo, Target};
+
+    /// Finds the program `with` refers to in the `App Paths` registry key, which `ShellExecute` also looks up.
+    pub fn find_app(with: &str) -> Option<AppInfo> {
+        let exe = if with.to_ascii_lowercase().ends_with(".exe") {
+            with.to_string()
+        } else {
+            format!("{with}.exe")
+        };
+        let key = HSTRING::from(format!(
+            r"SOFTWARE\Microsoft\Windows\CurrentVersion\App Paths\{exe}"
+        ));
+
+        [HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE]
+            .into_iter()
+            .find_map(|root| {
+                let mut len = 0u32;
+                // SAFETY: a null buffer queries the size of the value, in bytes
+                unsafe {
+                    RegGetValueW(
+                        root,
+                        &key,
+                        PCWSTR::null(),
+                        RRF_RT_REG_SZ,
+                        None,
+                        None,
+                        Some(&mut len),
+                    )
+                }
+                .ok()
+                .ok()?;
+                let mut buf = vec![0u16; len as usize / 2];
+                // SAFETY: `buf` holds `len` bytes
+                unsafe {
+                    RegGetValueW(
+                        root,
+                        &key,
+                        PCWSTR::null(),
+                        RRF_RT_REG_SZ,
+                        None,
+                        Some(buf.as_mut_ptr().cast()),
+                        Some(&mut len),
+                    )
+                }
+                .ok()
+                .ok()?;
+                let path = PathBuf::from(from_wide(&buf).trim_matches('"'));
+                path.is_file().then(|| app_info(path))
+            })
+    }
+
+    pub fn default_handler(target: &Target) -> Option<AppInfo> {
+        let assoc = match target {
+            Target::Url(url) => super::scheme(url).to_string(),
+            Target::Path(path) if path.is_dir() => "Folder".into(),
+            Target::Path(path) => format!(".{}", path.extension()?.to_string_lossy()),
+        };
+        let assoc = HSTRING::from(assoc);
+
+        let path = PathBuf::from(assoc_query(ASSOCSTR_EXECUTABLE, &assoc)?);
+        let name = assoc_query(ASSOCSTR_FRIENDLYAPPNAME, &assoc);
+        let app = app_info(path);
+        Some(AppInfo {
+            name: name.unwrap_or(app.name),
+            path: app.path,
+        })
+    }
+
+    /// Queries the `open` verb of the file type or URL scheme `assoc`.
+    fn assoc_query(kind: ASSOCSTR, assoc: &HSTRING) -> Option<String> {
+        let mut len = 0u32;
+        // SAFETY: a null buffer queries the length of the string, in characters
+        unsafe {
+            AssocQueryStringW(
+                ASSOCF_INIT_IGNOREUNKNOWN,
+                kind,
+                assoc,
+                w!("open"),
+                None,
+                &mut len,
+            )
+        }
+        .ok()
+        .ok()?;
+        let mut buf = vec![0u16; len as usize];
+        // SAFETY: `buf` holds `len` characters
+        unsafe {
+            AssocQueryStringW(
+                ASSOCF_INIT_IGNOREUNKNOWN,
+                kind,
+                assoc,
+                w!("open"),
+                Some(PWSTR(buf.as_mut_ptr())),
+                &mut len,
+            )
+        }
+        .ok()
+        .ok()?;
+        Some(from_wide(&buf)).filter(|value| !value.is_empty())
+    }
+
+    fn from_wide(buf: &[u16]) -> String {
+        let len = buf.iter().position(|c| *c == 0).unwrap_or(buf.len());
+        String::from_utf16_lossy(&buf[..len])
+    }
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+mod imp {
+    use std::{
+        path::{Path, PathBuf},
+        process::Command,
+    };
+
+    use super::{AppInfo, Target};
+
+    /// Programs are only looked up in `PATH`.
+    pub fn find_app(_with: &str) -> Option<AppInfo> {
+        None
+    }
+
+    pub fn default_handler(target: &Target) -> Option<AppInfo> {
+        let mime = match target {
+            Target::Url(url) => format!("x-scheme-handler/{}", super::scheme(url).to_lowercase()),
+            Target::Path(path) => {
+                xdg_mime(&["query".as_ref(), "filetype".as_ref(), path.as_os_str()])?
+            }
+        };
+        let desktop_id = xdg_mime(&["query".as_ref(), "default".as_ref(), mime.as_ref()])?;
+
+        let path = data_dirs()
+            .into_iter()
+            .map(|dir| dir.join("applications").join(&desktop_id))
+            .find(|path| path.is_file())?;
+        let name = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|entry| desktop_entry_name(&entry))
+            .unwrap_or_else(|| desktop_id.trim_end_matches(".desktop").into());
+        Some(AppInfo { name, path })
+    }
+
+    /// Runs `xdg-mime` and returns its output, `None` if it fails or outputs nothing.
+    fn xdg_mime(args: &[&std::ffi::OsStr]) -> Option<String> {
+        let output = Command::new("xdg-mime").args(args).output().ok()?;
+        let output = String::from_utf8(output.stdout).ok()?;
+        let output = output.trim();
+        (!output.is_empty()).then(|| output.to_string())
+    }
+
+    /// The directories desktop entries are looked up in, by order of precedence.
+    fn data_dirs() -> Vec<PathBuf> {
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")));
+        let data_dirs = std::env::var_os("XDG_DATA_DIRS")
+            .filter(|dirs| !dirs.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+        data_home
+            .into_iter()
+            .chain(std::env::split_paths(&data_dirs))
+            .collect()
+    }
+
+    /// The untranslated `Name` of a desktop entry.
+    pub(super) fn desktop_entry_name(entry: &str) -> Option<String> {
+        let mut in_desktop_entry = false;
+        for line in entry.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_desktop_entry = line == "[Desktop Entry]";
+            } else if in_desktop_entry {
+                if let Some(name) = line.strip_prefix("Name=") {
+                    return Some(name.trim().to_string());
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_programs() {
+        #[cfg(windows)]
+        let program = "cmd";
+        #[cfg(not(windows))]
+        let program = "sh";
+
+        let app = would_open("https://tauri.app", Some(program)).unwrap();
+        assert!(app.path.is_file());
+        assert_eq!(app.name, program);
+
+        let res = would_open("https://tauri.app", Some("tauri-opener-does-not-exist"));
+        assert!(
+            matches!(res, Err(crate::Error::NoHandler(target)) if target == "https://tauri.app")
+        );
+    }
+
+    #[test]
+    fn unknown_scheme_has_no_handler() {
+        let res = would_open("tauri-opener-unknown-scheme:payload", None);
+        assert!(matches!(res, Err(crate::Error::NoHandler(_))));
+    }
+
+    #[test]
+    fn missing_path_is_an_io_error() {
+        let res = would_open("/tauri-opener/does/not/exist", None);
+        assert!(matches!(res, Err(crate::Error::Io(_))));
+    }
+
+    #[test]
+    #[cfg(any(target_os = "macos", windows))]
+    fn resolves_default_browser() {
+        let app = would_open("https://tauri.app", None).unwrap();
+        assert!(!app.name.is_empty());
+        assert!(app.path.exists());
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "macos", windows)))]
+    fn reads_desktop_entry_names() {
+        let entry = "[Desktop Entry]\nName[fr]=Navigateur\nName=Web Browser\nExec=browser %u\n\n[Desktop Action new-window]\nName=New Window\n";
+        assert_eq!(
+            imp::desktop_entry_name(entry).as_deref(),
+            Some("Web Browser")
+        );
+        assert_eq!(
+            imp::desktop_entry_name("[Desktop Action new-window]\nName=New Window\n"),
+            None
+        );
+    }
+}
@@ -16,6 +16,8 @@ tauri::ios_plugin_binding!(init_plugin_opener);
 mod commands;
 mod config;
 mod error;
+#[cfg(desktop)]
+mod handler;
 mod open;
 mod reveal_item_in_dir;
 mod scope;
@@ -24,7 +26,11 @@ mod scope_entry;
 pub use error::Error;
 type Result<T> = std::result::Result<T, Error>;
 
-pub use open::{open_path, open_url};
+#[cfg(desktop)]
+pub use handler::{would_open, AppInfo};
+pub use open::{open_path, open_path_with_app_id, open_url, open_url_with_app_id, TextPosition};
+#[cfg(desktop)]
+pub use open::{open_path_spawn, open_text_file_at};
 pub use reveal_item_in_dir::{reveal_item_in_dir, reveal_items_in_dir};
 
 pub struct Opener<R: Runtime> {
@@ -58,7 +64,7 @@ impl<R: Runtime> Opener<R> {
     /// - **Android / iOS**: Always opens using default program, unless `with` is provided as "inAppBrowser".
     #[cfg(desktop)]
     pub fn open_url(&self, url: impl Into<String>, with: Option<impl Into<String>>) -> Result<()> {
-        crate::open::open(url.into(), with.map(Into::into))
+        crate::open::open_url(url.into(), with.map(Into::<String>::into))
     }
 
     /// Open a url with a default or specific program.
@@ -81,10 +87,12 @@ impl<R: Runtime> Opener<R> {
     /// - **Android / iOS**: Always opens using default program, unless `with` is provided as "inAppBrowser".
     #[cfg(mobile)]
     pub fn open_url(&self, url: impl Into<String>, with: Option<impl Into<String>>) -> Result<()> {
+        let url = url.into();
+        crate::open::validate_url(&url)?;
         self.mobile_plugin_handle
             .run_mobile_plugin(
                 "open",
-                serde_json::json!({ "url": url.into(), "with": with.map(Into::into) }),
+                serde_json::json!({ "url": url, "with": with.map(Into::into) }),
             )
             .map_err(Into::into)
     }
@@ -145,6 +153,67 @@ impl<R: Runtime> Opener<R> {
             .map_err(Into::into)
     }
 
+    /// Open a path with the program `with` and return the process id of the launched program.
+    ///
+    /// See [`open_path_spawn`] for the requirements on `with`.
+    #[cfg(desktop)]
+    pub fn open_path_spawn<I, A>(
+        &self,
+        path: impl Into<String>,
+        with: impl Into<String>,
+        args: I,
+    ) -> Result<u32>
+    where
+        I: IntoIterator<Item = A>,
+        A: AsRef<std::ffi::OsStr>,
+    {
+        open_path_spawn(path.into(), with.into(), args)
+    }
+
+    /// Open a text file in the editor `with`, with the cursor at `position`.
+    ///
+    /// See [`open_text_file_at`] for the supported editors.
+    #[cfg(desktop)]
+    pub fn open_text_file_at(
+        &self,
+        path: impl Into<String>,
+        position: TextPosition,
+        with: impl Into<String>,
+    ) -> Result<()> {
+        open_text_file_at(path.into(), position, with.into())
+    }
+
+    /// Resolve the application that would open `target` with [`Self::open_url`] or [`Self::open_path`],
+    /// without opening it.
+    ///
+    /// See [`would_open`] for how the application is resolved.
+    #[cfg(desktop)]
+    pub fn would_open(&self, target: &str, with: Option<&str>) -> Result<AppInfo> {
+        would_open(target, with)
+    }
+
+    /// Open a url with the application identified by `app_id`.
+    ///
+    /// See [`open_url_with_app_id`] for the expected identifier on each platform.
+    pub fn open_url_with_app_id(
+        &self,
+        url: impl Into<String>,
+        app_id: impl Into<String>,
+    ) -> Result<()> {
+        open_url_with_app_id(url.into(), app_id.into())
+    }
+
+    /// Open a path with the application identified by `app_id`.
+    ///
+    /// See [`open_path_with_app_id`] for the expected identifier on each platform.
+    pub fn open_path_with_app_id(
+        &self,
+        path: impl Into<String>,
+        app_id: impl Into<String>,
+    ) -> Result<()> {
+        open_path_with_app_id(path.into(), app_id.into())
+    }
+
     pub fn reveal_item_in_dir<P: AsRef<Path>>(&self, p: P) -> Result<()> {
         reveal_item_in_dir(p)
     }
@@ -4,7 +4,10 @@
 
 //! Types and functions related to shell.
 
-use std::{ffi::OsStr, path::Path};
+use std::{
+    ffi::{OsStr, OsString},
+    path::Path,
+};
 
 pub(crate) fn open<P: AsRef<OsStr>, S: AsRef<str>>(path: P, with: Option<S>) -> crate::Result<()> {
     match with {
@@ -32,9 +35,46 @@ pub(crate) fn open<P: AsRef<OsStr>, S: AsRef<str>>(path: P, with: Option<S>) ->
 /// ```
 pub fn open_url<P: AsRef<str>, S: AsRef<str>>(url: P, with: Option<S>) -> crate::Result<()> {
     let url = url.as_ref();
+    validate_url(url)?;
     open(url, with)
 }
 
+/// Rejects URLs that the platform opener could interpret as something else than a single URL.
+///
+/// The URL must start with a scheme and must not contain control characters, whitespace or quotes,
+/// which could end the argument early or be used to inject extra arguments to the opener program.
+/// Such characters must be percent-encoded instead, e.g. `mailto:me@tauri.app?subject=Hello%20World`.
+pub(crate) fn validate_url(url: &str) -> crate::Result<()> {
+    let invalid = |reason| {
+        Err(crate::Error::InvalidUrl {
+            url: url.escape_debug().to_string(),
+            reason,
+        })
+    };
+
+    if url.chars().any(char::is_control) {
+        return invalid("contains control characters");
+    }
+    if url.chars().any(|c| c.is_whitespace() || c == '"') {
+        return invalid("contains whitespace or quotes");
+    }
+
+    // scheme = ALPHA *( ALPHA / DIGIT / "+" / "-" / "." ), which also rules out a leading `-`,
+    // and a single letter is a Windows drive rather than a scheme
+    let scheme = url.split_once(':').map(|(scheme, _)| scheme);
+    let is_valid_scheme = scheme.is_some_and(|scheme| {
+        let mut chars = scheme.chars();
+        scheme.len() > 1
+            && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    });
+    if !is_valid_scheme {
+        return invalid("missing or invalid scheme");
+    }
+
+    Ok(())
+}
+
 /// Opens path with the program specified in `with`, or system default if `None`.
 ///
 /// ## Platform-specific:
@@ -59,3 +99,392 @@ pub fn open_path<P: AsRef<Path>, S: AsRef<str>>(path: P, with: Option<S>) -> cra
     }
     open(path, with)
 }
+
+/// Opens path by launching the program `with`, passing it `args` followed by the path,
+/// and returns the process id of the launched program.
+///
+/// Unlike [`open_path`], `with` must be an executable, either a path or a program name looked up in `PATH`.
+/// Opening with the default program is delegated to the system and has no process id to return.
+///
+/// ## Platform-specific:
+///
+/// - **macOS**: Application names such as `Safari` are not resolved, use the path to the executable
+///   inside the application bundle instead, e.g. `/Applications/Safari.app/Contents/MacOS/Safari`.
+/// - **Android / iOS**: Unsupported.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     // open the given file with Visual Studio Code in a new window
+///     let pid = tauri_plugin_opener::open_path_spawn("/path/to/file", "code", ["--new-window"])?;
+///     println!("launched editor with pid {pid}");
+///     Ok(())
+///   });
+/// ```
+#[cfg(desktop)]
+pub fn open_path_spawn<P, S, I, A>(path: P, with: S, args: I) -> crate::Result<u32>
+where
+    P: AsRef<Path>,
+    S: AsRef<OsStr>,
+    I: IntoIterator<Item = A>,
+    A: AsRef<OsStr>,
+{
+    let path = path.as_ref();
+    // Returns an IO error if not exists, and besides `exists()` is a shorthand for `metadata()`
+    _ = path.metadata()?;
+
+    spawn(
+        with,
+        args.into_iter()
+            .map(|arg| arg.as_ref().to_os_string())
+            .chain([path.as_os_str().to_os_string()]),
+    )
+}
+
+/// Launches `program` with `args`, detached from the standard streams, and returns its process id.
+#[cfg(desktop)]
+fn spawn<S: AsRef<OsStr>>(
+    program: S,
+    args: impl IntoIterator<Item = OsString>,
+) -> crate::Result<u32> {
+    let mut child = std::process::Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+    let pid = child.id();
+    // reap the process when it exits so it doesn't linger as a zombie
+    std::thread::spawn(move || child.wait());
+    Ok(pid)
+}
+
+/// A 1-based position in a text file, see [`open_text_file_at`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextPosition {
+    pub line: u32,
+    pub column: u32,
+}
+
+/// Opens the text file at `path` in the editor `with`, with the cursor at `position`.
+///
+/// The position is passed in the format of the editor, recognized by the name of the program:
+/// - `code`, `code-insiders`, `codium` and `cursor`: `-g path:line:column`
+/// - `subl` and `zed`: `path:line:column`
+/// - `vim`, `nvim` and `gvim`: `+line path`, or `nano`: `+line,column path`
+/// - `emacs` and `emacsclient`: `+line:column path`
+/// - `idea`, `webstorm`, `pycharm`, `clion`, `goland`, `rustrover` and `phpstorm`: `--line line --column column path`
+///
+/// `with` must be an executable for these editors, either a path or a program name looked up in `PATH`.
+/// Other programs are given the path only, like [`open_path`] does.
+///
+/// ## Platform-specific:
+///
+/// - **Windows**: Editors installed as a `.cmd` script must be passed with the extension, e.g. `code.cmd`.
+/// - **Android / iOS**: Unsupported.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri_plugin_opener::TextPosition;
+///
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     // open the given file in Visual Studio Code at line 12, column 5
+///     let position = TextPosition { line: 12, column: 5 };
+///     tauri_plugin_opener::open_text_file_at("/path/to/file.rs", position, "code")?;
+///     Ok(())
+///   });
+/// ```
+#[cfg(desktop)]
+pub fn open_text_file_at<P: AsRef<Path>, S: AsRef<str>>(
+    path: P,
+    position: TextPosition,
+    with: S,
+) -> crate::Result<()> {
+    let path = path.as_ref();
+    let with = with.as_ref();
+    // Returns an IO error if not exists, and besides `exists()` is a shorthand for `metadata()`
+    _ = path.metadata()?;
+
+    match editor_args(with, path, position) {
+        Some(args) => spawn(with, args).map(|_| ()),
+        None => open(path, Some(with)),
+    }
+}
+
+/// The arguments opening `path` at `position` in the editor `program`, `None` if the editor isn't known.
+#[cfg(desktop)]
+fn editor_args(program: &str, path: &Path, position: TextPosition) -> Option<Vec<OsString>> {
+    let TextPosition { line, column } = position;
+    let with_suffix = |suffix: String| {
+        let mut arg = path.as_os_str().to_os_string();
+        arg.push(suffix);
+        arg
+    };
+
+    let editor = Path::new(program).file_stem()?.to_str()?.to_lowercase();
+    let args = match editor.as_str() {
+        "code" | "code-insiders" | "codium" | "cursor" => {
+            vec!["-g".into(), with_suffix(format!(":{line}:{column}"))]
+        }
+        "subl" | "zed" => vec![with_suffix(format!(":{line}:{column}"))],
+        "vim" | "nvim" | "gvim" => vec![format!("+{line}").into(), path.into()],
+        "nano" => vec![format!("+{line},{column}").into(), path.into()],
+        "emacs" | "emacsclient" => vec![format!("+{line}:{column}").into(), path.into()],
+        "idea" | "idea64" | "webstorm" | "pycharm" | "clion" | "goland" | "rustrover"
+        | "phpstorm" => vec![
+            "--line".into(),
+            line.to_string().into(),
+            "--column".into(),
+            column.to_string().into(),
+            path.into(),
+        ],
+        _ => return None,
+    };
+    Some(args)
+}
+
+/// Opens URL with the application identified by `app_id`.
+///
+/// ## Platform-specific:
+///
+/// - **macOS**: `app_id` is the bundle identifier of the application, e.g. `com.microsoft.VSCode`.
+/// - **Windows**: `app_id` is the Application User Model ID of the application, e.g. `Microsoft.WindowsNotepad_8wekyb3d8bbwe!App`.
+/// - **Linux / Android / iOS**: Unsupported.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     // open the given URL with Safari
+///     tauri_plugin_opener::open_url_with_app_id("https://github.com/tauri-apps/tauri", "com.apple.Safari")?;
+///     Ok(())
+///   });
+/// ```
+pub fn open_url_with_app_id<P: AsRef<str>, S: AsRef<str>>(url: P, app_id: S) -> crate::Result<()> {
+    let url = url.as_ref();
+    validate_url(url)?;
+    imp::open_with_app_id(url.as_ref(), app_id.as_ref(), true)
+}
+
+/// Opens path with the application identified by `app_id`.
+///
+/// ## Platform-specific:
+///
+/// - **macOS**: `app_id` is the bundle identifier of the application, e.g. `com.microsoft.VSCode`.
+/// - **Windows**: `app_id` is the Application User Model ID of the application, e.g. `Microsoft.WindowsNotepad_8wekyb3d8bbwe!App`.
+/// - **Linux / Android / iOS**: Unsupported.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     // open the given file with Visual Studio Code
+///     tauri_plugin_opener::open_path_with_app_id("/path/to/file", "com.microsoft.VSCode")?;
+///     Ok(())
+///   });
+/// ```
+pub fn open_path_with_app_id<P: AsRef<Path>, S: AsRef<str>>(
+    path: P,
+    app_id: S,
+) -> crate::Result<()> {
+    let path = path.as_ref();
+    // Returns an IO error if not exists, and besides `exists()` is a shorthand for `metadata()`
+    _ = path.metadata()?;
+    imp::open_with_app_id(path.as_os_str(), app_id.as_ref(), false)
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::{ffi::OsStr, process::Command};
+
+    pub fn open_with_app_id(target: &OsStr, app_id: &str, _is_url: bool) -> crate::Result<()> {
+        // `open -b` resolves the bundle identifier through Launch Services
+        let output = Command::new("open")
+            .arg("-b")
+            .arg(app_id)
+            .arg(target)
+            .output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(crate::Error::UnknownAppId(app_id.into()))
+        }
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::ffi::OsStr;
+
+    use windows::{
+        core::{w, HSTRING},
+        Win32::{
+            System::Com::{CoCreateInstance, CoInitialize, CLSCTX_LOCAL_SERVER},
+            UI::Shell::{
+                ApplicationActivationManager, IApplicationActivationManager, IShellItem,
+                IShellItemArray, SHCreateItemFromParsingName, SHCreateShellItemArrayFromShellItem,
+            },
+        },
+    };
+
+    pub fn open_with_app_id(target: &OsStr, app_id: &str, is_url: bool) -> crate::Result<()> {
+        let _ = unsafe { CoInitialize(None) };
+
+        let manager: IApplicationActivationManager =
+            unsafe { CoCreateInstance(&ApplicationActivationManager, None, CLSCTX_LOCAL_SERVER) }?;
+
+        let item: IShellItem =
+            unsafe { SHCreateItemFromParsingName(&HSTRING::from(target), None) }?;
+        let items: IShellItemArray = unsafe { SHCreateShellItemArrayFromShellItem(&item) }?;
+
+        let app_id_hstring = HSTRING::from(app_id);
+        let result = if is_url {
+            unsafe { manager.ActivateForProtocol(&app_id_hstring, &items) }
+        } else {
+            unsafe { manager.ActivateForFile(&app_id_hstring, &items, w!("open")) }
+        };
+
+        result
+            .map(|_process_id| ())
+            .map_err(|_| crate::Error::UnknownAppId(app_id.into()))
+    }
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+mod imp {
+    use std::ffi::OsStr;
+
+    pub fn open_with_app_id(_target: &OsStr, _app_id: &str, _is_url: bool) -> crate::Result<()> {
+        Err(crate::Error::UnsupportedPlatform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(desktop)]
+    fn spawn_returns_pid() {
+        #[cfg(windows)]
+        let (program, args) = ("cmd", ["/C", "rem"]);
+        #[cfg(not(windows))]
+        let (program, args) = ("true", ["--"]);
+
+        let pid = super::open_path_spawn(std::env::temp_dir(), program, args).unwrap();
+        assert_ne!(pid, 0);
+
+        let res = super::open_path_spawn(
+            std::env::temp_dir(),
+            "tauri-opener-does-not-exist",
+            [] as [&str; 0],
+        );
+        assert!(matches!(res, Err(crate::Error::Io(_))));
+    }
+
+    #[test]
+    #[cfg(desktop)]
+    fn formats_editor_positions() {
+        use super::{editor_args, TextPosition};
+        use std::path::Path;
+
+        let path = Path::new("src/main.rs");
+        let position = TextPosition {
+            line: 12,
+            column: 5,
+        };
+        let args = |program| {
+            editor_args(program, path, position).map(|args| {
+                args.into_iter()
+                    .map(|arg| arg.into_string().unwrap())
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        assert_eq!(args("code").unwrap(), ["-g", "src/main.rs:12:5"]);
+        assert_eq!(
+            args("/usr/local/bin/code-insiders").unwrap(),
+            ["-g", "src/main.rs:12:5"]
+        );
+        assert_eq!(args("Code.exe").unwrap(), ["-g", "src/main.rs:12:5"]);
+        assert_eq!(args("subl").unwrap(), ["src/main.rs:12:5"]);
+        assert_eq!(args("nvim").unwrap(), ["+12", "src/main.rs"]);
+        assert_eq!(
+            args("idea").unwrap(),
+            ["--line", "12", "--column", "5", "src/main.rs"]
+        );
+        assert_eq!(args("notepad"), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn errors_on_unknown_bundle_id() {
+        let res = super::open_url_with_app_id("https://tauri.app", "app.tauri.does-not-exist");
+        assert!(
+            matches!(res, Err(crate::Error::UnknownAppId(id)) if id == "app.tauri.does-not-exist")
+        );
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn errors_on_unknown_aumid() {
+        let res = super::open_url_with_app_id("https://tauri.app", "Tauri.DoesNotExist!App");
+        assert!(
+            matches!(res, Err(crate::Error::UnknownAppId(id)) if id == "Tauri.DoesNotExist!App")
+        );
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "macos", windows)))]
+    fn app_id_is_unsupported() {
+        let res = super::open_url_with_app_id("https://tauri.app", "app.tauri.example");
+        assert!(matches!(res, Err(crate::Error::UnsupportedPlatform)));
+    }
+
+    #[test]
+    fn accepts_benign_urls() {
+        for url in [
+            "https://tauri.app",
+            "http://localhost:1420/path?query=a%20b#hash",
+            "mailto:me@tauri.app?subject=Hello%20World",
+            "tel:+1-555-0100",
+            "file:///home/user/My%20Documents/file.txt",
+            "vscode://file/path/to/file",
+            "x-custom.scheme+v1:payload",
+        ] {
+            assert!(super::validate_url(url).is_ok(), "{url}");
+        }
+    }
+
+    #[test]
+    fn rejects_malicious_urls() {
+        for url in [
+            "",
+            "--help",
+            "-a Calculator",
+            "https://tauri.app\n--new-window",
+            "https://tauri.app\0",
+            "https://tauri.app\r\nInjected: header",
+            "file:///C:/Windows/System32/cmd.exe /c calc",
+            "https://tauri.app\t--flag",
+            "https://tauri.app\" --flag",
+            "custom:\u{2028}payload",
+            "/usr/bin/xterm",
+            "C:\\Windows\\System32\\calc.exe",
+            "1http://tauri.app",
+            "ht tp://tauri.app",
+        ] {
+            assert!(
+                matches!(
+                    super::validate_url(url),
+                    Err(crate::Error::InvalidUrl { .. })
+                ),
+                "{url:?}"
+            );
+        }
+    }
+}
@@ -6,6 +6,9 @@ const COMMANDS: &[&str] = &[
     "move_window",
     "move_window_constrained",
     "set_tray_icon_state",
+    "restore_position",
+    "resize_and_move",
+    "move_window_at_cursor",
 ];
 
 fn main() {
@@ -24,7 +24,15 @@ export enum Position {
   TrayRight,
   TrayBottomRight,
   TrayCenter,
-  TrayBottomCenter
+  TrayBottomCenter,
+  /**
+   * The top-left corner of the window at the mouse cursor, constrained to the monitor the cursor is on.
+   *
+   * Unsupported on Linux (Wayland).
+   *
+   * @since 2.4.0
+   */
+  Cursor
 }
 
 /**
@@ -39,6 +47,74 @@ export async function moveWindow(to: Position): Promise<void> {
   })
 }
 
+/**
+ * Moves the `Window` to the mouse cursor like {@link Position.Cursor},
+ * moved by the given offset in physical pixels.
+ *
+ * The window is constrained to the monitor the cursor is on.
+ * Unsupported on Linux (Wayland).
+ *
+ * @example
+ * ```typescript
+ * import { moveWindowAtCursor } from '@tauri-apps/plugin-positioner';
+ * await moveWindowAtCursor({ x: 8, y: 8 });
+ * ```
+ *
+ * @param offset The offset from the cursor in physical pixels.
+ *
+ * @since 2.4.0
+ */
+export async function moveWindowAtCursor(offset: {
+  x: number
+  y: number
+}): Promise<void> {
+  await invoke('plugin:positioner|move_window_at_cursor', {
+    offset
+  })
+}
+
+/**
+ * Sets the inner size of the `Window` from a logical size, then moves it to the given {@link Position}
+ * computed with that new size, e.g. to fit a tray popover to its content.
+ *
+ * Both the size and the position are constrained to the work area of the monitor.
+ *
+ * @example
+ * ```typescript
+ * import { resizeAndMove, Position } from '@tauri-apps/plugin-positioner';
+ * await resizeAndMove({ width: 320, height: 480 }, Position.TrayCenter);
+ * ```
+ *
+ * @param size The logical inner size of the window.
+ * @param to The {@link Position} to move to.
+ *
+ * @since 2.4.0
+ */
+export async function resizeAndMove(
+  size: { width: number; height: number },
+  to: Position
+): Promise<void> {
+  await invoke('plugin:positioner|resize_and_move', {
+    size,
+    position: to
+  })
+}
+
+/**
+ * Moves the `Window` back to the {@link Position} it was last moved to with {@link moveWindow},
+ * computed against the monitor it is currently on.
+ *
+ * Requires the plugin to be built with `Builder::persist_positions()`.
+ * Tray and cursor positions are not saved.
+ *
+ * @returns `false` if no position was saved for the window.
+ *
+ * @since 2.4.0
+ */
+export async function restorePosition(): Promise<boolean> {
+  return await invoke('plugin:positioner|restore_position')
+}
+
 /**
  * Moves the `Window` to the given {@link Position} using `WindowExt.move_window_constrained()`
  *
@@ -3,17 +3,17 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use crate::store::PositionStore;
 #[cfg(feature = "tray-icon")]
 use crate::Tray;
 use serde_repr::Deserialize_repr;
-#[cfg(feature = "tray-icon")]
-use tauri::Manager;
-#[cfg(feature = "tray-icon")]
-use tauri::Monitor;
-use tauri::{PhysicalPosition, PhysicalSize, Result, Runtime, WebviewWindow, Window};
+use tauri::{
+    LogicalSize, Manager, Monitor, PhysicalPosition, PhysicalRect, PhysicalSize, Result, Runtime,
+    WebviewWindow, Window,
+};
 
 /// Well known window positions.
-#[derive(Debug, Deserialize_repr)]
+#[derive(Debug, Clone, Copy, Deserialize_repr)]
 #[repr(u16)]
 pub enum Position {
     TopLeft = 0,
@@ -37,6 +37,70 @@ pub enum Position {
     TrayCenter,
     #[cfg(feature = "tray-icon")]
     TrayBottomCenter,
+    /// The top-left corner of the window at the mouse cursor,
+    /// constrained to the monitor the cursor is on.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Linux (Wayland)**: Unsupported, the cursor position can't be queried.
+    ///
+    /// See [`WindowExt::move_window_at_cursor`] to move the window away from the cursor.
+    Cursor = 15,
+}
+
+impl Position {
+    pub(crate) fn from_discriminant(discriminant: u16) -> Option<Self> {
+        use Position::*;
+
+        let position = match discriminant {
+            0 => TopLeft,
+            1 => TopRight,
+            2 => BottomLeft,
+            3 => BottomRight,
+            4 => TopCenter,
+            5 => BottomCenter,
+            6 => LeftCenter,
+            7 => RightCenter,
+            8 => Center,
+            #[cfg(feature = "tray-icon")]
+            9 => TrayLeft,
+            #[cfg(feature = "tray-icon")]
+            10 => TrayBottomLeft,
+            #[cfg(feature = "tray-icon")]
+            11 => TrayRight,
+            #[cfg(feature = "tray-icon")]
+            12 => TrayBottomRight,
+            #[cfg(feature = "tray-icon")]
+            13 => TrayCenter,
+            #[cfg(feature = "tray-icon")]
+            14 => TrayBottomCenter,
+            15 => Cursor,
+            _ => return None,
+        };
+        Some(position)
+    }
+
+    pub(crate) fn discriminant(&self) -> u16 {
+        *self as u16
+    }
+
+    /// Whether the position only depends on the current monitor, so it can be re-applied on the next launch.
+    pub(crate) fn is_monitor_relative(&self) -> bool {
+        use Position::*;
+
+        matches!(
+            self,
+            TopLeft
+                | TopRight
+                | BottomLeft
+                | BottomRight
+                | TopCenter
+                | BottomCenter
+                | LeftCenter
+                | RightCenter
+                | Center
+        )
+    }
 }
 
 /// A [`Window`] extension that provides extra methods related to positioning.
@@ -45,6 +109,15 @@ pub trait WindowExt {
     ///
     /// All (non-tray) positions are relative to the **current** screen.
     fn move_window(&self, position: Position) -> Result<()>;
+    /// Moves the [`Window`] to the mouse cursor like [`Position::Cursor`],
+    /// moved by `offset` in physical pixels, e.g. to keep a popover from covering the cursor.
+    ///
+    /// The window is constrained to the monitor the cursor is on.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Linux (Wayland)**: Unsupported, the cursor position can't be queried.
+    fn move_window_at_cursor(&self, offset: PhysicalPosition<i32>) -> Result<()>;
     #[cfg(feature = "tray-icon")]
     /// Moves the [`Window`] to the given [`Position`] while constraining Tray Positions to the dimensions of the screen.
     ///
@@ -53,6 +126,18 @@ pub trait WindowExt {
     /// This method allows you to position your Tray Windows without having them
     /// cut off on the screen borders.
     fn move_window_constrained(&self, position: Position) -> Result<()>;
+    /// Sets the inner size of the [`Window`] from a logical size, then moves it to the given [`Position`]
+    /// computed with that new size, e.g. to fit a tray popover to its content.
+    ///
+    /// The size is converted with the scale factor of the monitor the window is moved to,
+    /// and both the size and the position are constrained to the work area of that monitor.
+    fn resize_and_move(&self, size: LogicalSize<f64>, position: Position) -> Result<()>;
+    /// Moves the [`Window`] to the [`Position`] it was last moved to with [`WindowExt::move_window`],
+    /// saved with [`Builder::persist_positions`](crate::Builder::persist_positions).
+    ///
+    /// The position is computed against the monitor the window is currently on, so it follows resolution changes.
+    /// Returns `false` if no position was saved for the window.
+    fn move_to_saved_position(&self) -> Result<bool>;
 }
 
 impl<R: Runtime> WindowExt for WebviewWindow<R> {
@@ -60,10 +145,22 @@ impl<R: Runtime> WindowExt for WebviewWindow<R> {
         self.as_ref().window().move_window(pos)
     }
 
+    fn move_window_at_cursor(&self, offset: PhysicalPosition<i32>) -> Result<()> {
+        self.as_ref().window().move_window_at_cursor(offset)
+    }
+
     #[cfg(feature = "tray-icon")]
     fn move_window_constrained(&self, position: Position) -> Result<()> {
         self.as_ref().window().move_window_constrained(position)
     }
+
+    fn resize_and_move(&self, size: LogicalSize<f64>, position: Position) -> Result<()> {
+        self.as_ref().window().resize_and_move(size, position)
+    }
+
+    fn move_to_saved_position(&self) -> Result<bool> {
+        self.as_ref().window().move_to_saved_position()
+    }
 }
 
 impl<R: Runtime> WindowExt for Window<R> {
@@ -82,7 +179,7 @@ impl<R: Runtime> WindowExt for Window<R> {
             return self.move_window(position);
         }
 
-        let window_position = calculate_position(self, position)?;
+        let window_position = calculate_position(self, &position)?;
         let monitor = get_monitor_for_tray_icon(self)?;
         if let Some(monitor) = monitor {
             let monitor_size = monitor.size();
@@ -125,9 +222,145 @@ impl<R: Runtime> WindowExt for Window<R> {
     }
 
     fn move_window(&self, pos: Position) -> Result<()> {
-        let position = calculate_position(self, pos)?;
+        let position = calculate_position(self, &pos)?;
+        self.set_position(position)?;
+        save_position(self, &pos);
+        Ok(())
+    }
+
+    fn move_window_at_cursor(&self, offset: PhysicalPosition<i32>) -> Result<()> {
+        let window_size = PhysicalSize::<i32> {
+            width: self.outer_size()?.width as i32,
+            height: self.outer_size()?.height as i32,
+        };
+        let position = calculate_cursor_position(self, window_size, offset)?;
         self.set_position(position)
     }
+
+    fn resize_and_move(&self, size: LogicalSize<f64>, position: Position) -> Result<()> {
+        let Some(monitor) = target_monitor(self, &position)? else {
+            self.set_size(size)?;
+            return self.move_window(position);
+        };
+
+        let (outer_size, inner_size) = (self.outer_size()?, self.inner_size()?);
+        let decorations = PhysicalSize::<i32> {
+            width: outer_size.width as i32 - inner_size.width as i32,
+            height: outer_size.height as i32 - inner_size.height as i32,
+        };
+        let (inner_size, physical_pos) = fit_to_work_area(
+            size.to_physical(monitor.scale_factor()),
+            decorations,
+            monitor.work_area(),
+            |window_size| calculate_position_with_size(self, &position, window_size),
+        )?;
+
+        // everything is computed upfront so the window is only resized and moved once
+        self.set_size(inner_size)?;
+        self.set_position(physical_pos)?;
+        save_position(self, &position);
+        Ok(())
+    }
+
+    fn move_to_saved_position(&self) -> Result<bool> {
+        let position = self
+            .try_state::<PositionStore>()
+            .and_then(|store| store.get(self.label()));
+        match position {
+            Some(position) => self.move_window(position).map(|()| true),
+            None => Ok(false),
+        }
+    }
+}
+
+/// Remembers the position the window was moved to if [`Builder::persist_positions`](crate::Builder::persist_positions) is enabled.
+///
+/// The window has already been moved, so failing to save its position is only logged.
+fn save_position<R: Runtime>(window: &Window<R>, position: &Position) {
+    if let Some(store) = window.try_state::<PositionStore>() {
+        if let Err(e) = store.save(window.label(), position) {
+            log::warn!(
+                "failed to save the position of window {}: {e}",
+                window.label()
+            );
+        }
+    }
+}
+
+/// The monitor the window is placed on for `position`.
+fn target_monitor<R: Runtime>(window: &Window<R>, position: &Position) -> Result<Option<Monitor>> {
+    match position {
+        #[cfg(feature = "tray-icon")]
+        Position::TrayLeft
+        | Position::TrayBottomLeft
+        | Position::TrayRight
+        | Position::TrayBottomRight
+        | Position::TrayCenter
+        | Position::TrayBottomCenter => get_monitor_for_tray_icon(window),
+        Position::Cursor => {
+            let cursor = window.cursor_position()?;
+            Ok(window
+                .monitor_from_point(cursor.x, cursor.y)?
+                .or(window.current_monitor()?))
+        }
+        _ => window.current_monitor(),
+    }
+}
+
+/// Shrinks `inner_size` so the window fits in `work_area`, then places it with `place`
+/// from its outer size, keeping it in `work_area`.
+///
+/// Returns the inner size and the position of the window.
+fn fit_to_work_area(
+    inner_size: PhysicalSize<i32>,
+    decorations: PhysicalSize<i32>,
+    work_area: &PhysicalRect<i32, u32>,
+    place: impl FnOnce(PhysicalSize<i32>) -> Result<PhysicalPosition<i32>>,
+) -> Result<(PhysicalSize<i32>, PhysicalPosition<i32>)> {
+    let work_area_size = PhysicalSize::<i32> {
+        width: work_area.size.width as i32,
+        height: work_area.size.height as i32,
+    };
+    let inner_size = PhysicalSize {
+        width: inner_size
+            .width
+            .min(work_area_size.width - decorations.width)
+            .max(0),
+        height: inner_size
+            .height
+            .min(work_area_size.height - decorations.height)
+            .max(0),
+    };
+    let outer_size = PhysicalSize {
+        width: inner_size.width + decorations.width,
+        height: inner_size.height + decorations.height,
+    };
+    let position = clamp_to_monitor(
+        place(outer_size)?,
+        outer_size,
+        work_area.position,
+        work_area_size,
+    );
+    Ok((inner_size, position))
+}
+
+/// Restores the positions saved with [`Builder::persist_positions`](crate::Builder::persist_positions).
+pub trait PositionerExt<R: Runtime> {
+    /// Moves the webview window `label` to the [`Position`] it was last moved to with [`WindowExt::move_window`],
+    /// see [`WindowExt::move_to_saved_position`].
+    ///
+    /// Returns `false` if no position was saved for the window, or if it doesn't exist.
+    /// Use [`WindowExt::move_to_saved_position`] for windows that are not webview windows.
+    fn restore_position(&self, label: &str) -> Result<bool>;
+}
+
+impl<R: Runtime, T: Manager<R>> PositionerExt<R> for T {
+    fn restore_position(&self, label: &str) -> Result<bool> {
+        match self.get_webview_window(label) {
+            Some(window) => window.move_to_saved_position(),
+            None => Ok(false),
+        }
+    }
 }
 
 #[cfg(feature = "tray-icon")]
@@ -144,11 +377,72 @@ fn get_monitor_for_tray_icon<R: Runtime>(window: &Window<R>) -> Result<Option<Mo
     window.monitor_from_point(tray_position.x, tray_position.y)
 }
 
+/// Calculate the top-left position of the window placed at the cursor, moved by `offset`
+/// and constrained to the monitor the cursor is on.
+fn calculate_cursor_position<R: Runtime>(
+    window: &Window<R>,
+    window_size: PhysicalSize<i32>,
+    offset: PhysicalPosition<i32>,
+) -> Result<PhysicalPosition<i32>> {
+    let cursor = window.cursor_position()?;
+    let position = PhysicalPosition {
+        x: cursor.x as i32 + offset.x,
+        y: cursor.y as i32 + offset.y,
+    };
+
+    let Some(monitor) = window
+        .monitor_from_point(cursor.x, cursor.y)?
+        .or(window.current_monitor()?)
+    else {
+        return Ok(position);
+    };
+
+    let monitor_size = PhysicalSize::<i32> {
+        width: monitor.size().width as i32,
+        height: monitor.size().height as i32,
+    };
+    Ok(clamp_to_monitor(
+        position,
+        window_size,
+        *monitor.position(),
+        monitor_size,
+    ))
+}
+
+/// Moves `position` so a window of `window_size` fits on the monitor,
+/// preferring to keep the top-left corner visible if it can't fit.
+fn clamp_to_monitor(
+    position: PhysicalPosition<i32>,
+    window_size: PhysicalSize<i32>,
+    monitor_position: PhysicalPosition<i32>,
+    monitor_size: PhysicalSize<i32>,
+) -> PhysicalPosition<i32> {
+    let max_x = monitor_position.x + monitor_size.width - window_size.width;
+    let max_y = monitor_position.y + monitor_size.height - window_size.height;
+    PhysicalPosition {
+        x: position.x.min(max_x).max(monitor_position.x),
+        y: position.y.min(max_y).max(monitor_position.y),
+    }
+}
+
 /// Calculate the top-left position of the window based on the given
 /// [`Position`].
 fn calculate_position<R: Runtime>(
     window: &Window<R>,
-    pos: Position,
+    pos: &Position,
+) -> Result<PhysicalPosition<i32>> {
+    let window_size = PhysicalSize::<i32> {
+        width: window.outer_size()?.width as i32,
+        height: window.outer_size()?.height as i32,
+    };
+    calculate_position_with_size(window, pos, window_size)
+}
+
+/// Same as [`calculate_position`] for a window of the given outer size.
+fn calculate_position_with_size<R: Runtime>(
+    window: &Window<R>,
+    pos: &Position,
+    window_size: PhysicalSize<i32>,
 ) -> Result<PhysicalPosition<i32>> {
     use Position::*;
 
@@ -160,10 +454,6 @@ fn calculate_position<R: Runtime>(
         width: screen.size().width as i32,
         height: screen.size().height as i32,
     };
-    let window_size = PhysicalSize::<i32> {
-        width: window.outer_size()?.width as i32,
-        height: window.outer_size()?.height as i32,
-    };
     #[cfg(feature = "tray-icon")]
     let (tray_position, tray_size) = window
         .state::<Tray>()
@@ -179,6 +469,7 @@ fn calculate_position<R: Runtime>(
         .unwrap_or_default();
 
     let physical_pos = match pos {
+        Cursor => calculate_cursor_position(window, window_size, PhysicalPosition::default())?,
         TopLeft => *screen_position,
         TopRight => PhysicalPosition {
             x: screen_position.x + (screen_size.width - window_size.width),
@@ -305,3 +596,117 @@ fn calculate_position<R: Runtime>(
 
     Ok(physical_pos)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONITOR_POSITION: PhysicalPosition<i32> = PhysicalPosition { x: 1920, y: 0 };
+    const MONITOR_SIZE: PhysicalSize<i32> = PhysicalSize {
+        width: 1920,
+        height: 1080,
+    };
+    const WINDOW_SIZE: PhysicalSize<i32> = PhysicalSize {
+        width: 300,
+        height: 200,
+    };
+
+    #[test]
+    fn clamps_cursor_near_bottom_right_edge() {
+        let cursor = PhysicalPosition { x: 3800, y: 1050 };
+        let position = clamp_to_monitor(cursor, WINDOW_SIZE, MONITOR_POSITION, MONITOR_SIZE);
+        assert_eq!(position, PhysicalPosition { x: 3540, y: 880 });
+    }
+
+    #[test]
+    fn clamps_offset_past_top_left_edge() {
+        let cursor = PhysicalPosition { x: 1900, y: -10 };
+        let position = clamp_to_monitor(cursor, WINDOW_SIZE, MONITOR_POSITION, MONITOR_SIZE);
+        assert_eq!(position, MONITOR_POSITION);
+    }
+
+    #[test]
+    fn keeps_cursor_position_that_fits() {
+        let cursor = PhysicalPosition { x: 2500, y: 400 };
+        let position = clamp_to_monitor(cursor, WINDOW_SIZE, MONITOR_POSITION, MONITOR_SIZE);
+        assert_eq!(position, cursor);
+    }
+
+    #[test]
+    fn positions_with_the_new_size() {
+        let work_area = PhysicalRect {
+            position: MONITOR_POSITION,
+            // the taskbar takes the bottom 40 pixels
+            size: PhysicalSize {
+                width: 1920,
+                height: 1040,
+            },
+        };
+        let decorations = PhysicalSize {
+            width: 0,
+            height: 30,
+        };
+        let inner_size = LogicalSize::new(400.0, 300.0).to_physical(1.5);
+
+        let (size, position) =
+            fit_to_work_area(inner_size, decorations, &work_area, |outer_size| {
+                // bottom right of the whole monitor
+                assert_eq!(outer_size, PhysicalSize::new(600, 480));
+                Ok(PhysicalPosition {
+                    x: MONITOR_POSITION.x + MONITOR_SIZE.width - outer_size.width,
+                    y: MONITOR_SIZE.height - outer_size.height,
+                })
+            })
+            .unwrap();
+        assert_eq!(size, PhysicalSize::new(600, 450));
+        // moved above the taskbar
+        assert_eq!(position, PhysicalPosition::new(3240, 560));
+    }
+
+    #[test]
+    fn shrinks_to_the_work_area() {
+        let work_area = PhysicalRect {
+            position: MONITOR_POSITION,
+            size: PhysicalSize {
+                width: 1920,
+                height: 1040,
+            },
+        };
+        let decorations = PhysicalSize {
+            width: 0,
+            height: 30,
+        };
+
+        let (size, position) = fit_to_work_area(
+            PhysicalSize::new(2500, 1200),
+            decorations,
+            &work_area,
+            |_| Ok(PhysicalPosition::new(2000, 100)),
+        )
+        .unwrap();
+        assert_eq!(size, PhysicalSize::new(1920, 1010));
+        assert_eq!(position, MONITOR_POSITION);
+    }
+
+    #[test]
+    fn deserializes_positions() {
+        assert!(matches!(
+            serde_json::from_str::<Position>("8").unwrap(),
+            Position::Center
+        ));
+        assert!(matches!(
+            serde_json::from_str::<Position>("15").unwrap(),
+            Position::Cursor
+        ));
+        assert!(serde_json::from_str::<Position>("42").is_err());
+    }
+
+    #[test]
+    fn discriminants_round_trip() {
+        for discriminant in 0..16 {
+            if let Some(position) = Position::from_discriminant(discriminant) {
+                assert_eq!(position.discriminant(), discriminant);
+            }
+        }
+    }
+}
@@ -5,6 +5,8 @@
 
 //! A plugin for Tauri that helps position your windows at well-known locations.
 //!
+//! Use [`Builder::persist_positions`] to remember the position of each window across launches.
+//!
 //! # Cargo features
 //!
 //! - **tray-icon**: Enables tray-icon-relative positions.
@@ -18,15 +20,17 @@
 #![cfg(not(any(target_os = "android", target_os = "ios")))]
 
 mod ext;
+mod store;
 
 pub use ext::*;
+use store::PositionStore;
 use tauri::{
     plugin::{self, TauriPlugin},
-    Result, Runtime,
+    LogicalSize, Manager, PhysicalPosition, Result, Runtime,
 };
 
 #[cfg(feature = "tray-icon")]
-use tauri::{tray::TrayIconEvent, AppHandle, Manager, PhysicalPosition, PhysicalSize};
+use tauri::{tray::TrayIconEvent, AppHandle, PhysicalSize};
 
 #[cfg(feature = "tray-icon")]
 struct Tray(std::sync::Mutex<Option<(PhysicalPosition<f64>, PhysicalSize<f64>)>>);
@@ -61,6 +65,28 @@ async fn move_window<R: Runtime>(window: tauri::Window<R>, position: Position) -
     window.move_window(position)
 }
 
+#[tauri::command]
+async fn move_window_at_cursor<R: Runtime>(
+    window: tauri::Window<R>,
+    offset: PhysicalPosition<i32>,
+) -> Result<()> {
+    window.move_window_at_cursor(offset)
+}
+
+#[tauri::command]
+async fn resize_and_move<R: Runtime>(
+    window: tauri::Window<R>,
+    size: LogicalSize<f64>,
+    position: Position,
+) -> Result<()> {
+    window.resize_and_move(size, position)
+}
+
+#[tauri::command]
+async fn restore_position<R: Runtime>(window: tauri::Window<R>) -> Result<bool> {
+    window.move_to_saved_position()
+}
+
 #[cfg(feature = "tray-icon")]
 #[tauri::command]
 async fn move_window_constrained<R: Runtime>(
@@ -84,21 +110,54 @@ fn set_tray_icon_state<R: Runtime>(
         .replace((position, size));
 }
 
+/// Builds the positioner plugin.
+#[derive(Default)]
+pub struct Builder {
+    persist_positions: bool,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Saves the [`Position`] each window was last moved to with [`WindowExt::move_window`]
+    /// in the app config directory, so [`PositionerExt::restore_position`] can re-apply it on the next launch.
+    ///
+    /// Only the positions relative to the monitor are saved, the tray and cursor positions are not.
+    pub fn persist_positions(mut self) -> Self {
+        self.persist_positions = true;
+        self
+    }
+
+    pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
+        plugin::Builder::new("positioner")
+            .invoke_handler(tauri::generate_handler![
+                move_window,
+                move_window_at_cursor,
+                resize_and_move,
+                restore_position,
+                #[cfg(feature = "tray-icon")]
+                move_window_constrained,
+                #[cfg(feature = "tray-icon")]
+                set_tray_icon_state
+            ])
+            .setup(move |app_handle, _api| {
+                #[cfg(feature = "tray-icon")]
+                app_handle.manage(Tray(std::sync::Mutex::new(None)));
+
+                if self.persist_positions {
+                    let path = app_handle.path().app_config_dir()?.join(store::FILENAME);
+                    app_handle.manage(PositionStore::load(path));
+                }
+
+                Ok(())
+            })
+            .build()
+    }
+}
+
 /// The Tauri plugin that exposes [`WindowExt::move_window`] to the webview.
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    let plugin = plugin::Builder::new("positioner").invoke_handler(tauri::generate_handler![
-        move_window,
-        #[cfg(feature = "tray-icon")]
-        move_window_constrained,
-        #[cfg(feature = "tray-icon")]
-        set_tray_icon_state
-    ]);
-
-    #[cfg(feature = "tray-icon")]
-    let plugin = plugin.setup(|app_handle, _api| {
-        app_handle.manage(Tray(std::sync::Mutex::new(None)));
-        Ok(())
-    });
-
-    plugin.build()
+    Builder::new().build()
 }
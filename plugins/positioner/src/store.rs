@@ -0,0 +1,82 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, path::PathBuf, sync::Mutex};
+
+use tauri::Result;
+
+use crate::Position;
+
+pub(crate) const FILENAME: &str = ".positioner.json";
+
+/// The positions windows were last moved to, see [`Builder::persist_positions`](crate::Builder::persist_positions).
+pub(crate) struct PositionStore {
+    path: PathBuf,
+    positions: Mutex<HashMap<String, u16>>,
+}
+
+impl PositionStore {
+    /// Loads the positions saved at `path`, starting empty if the file is missing or invalid.
+    pub(crate) fn load(path: PathBuf) -> Self {
+        let positions = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            positions: Mutex::new(positions),
+        }
+    }
+
+    /// Remembers `position` for the window `label`, ignoring the positions that are not relative to the monitor.
+    pub(crate) fn save(&self, label: &str, position: &Position) -> Result<()> {
+        if !position.is_monitor_relative() {
+            return Ok(());
+        }
+        let discriminant = position.discriminant();
+
+        let mut positions = self.positions.lock().unwrap();
+        if positions.insert(label.into(), discriminant) == Some(discriminant) {
+            return Ok(());
+        }
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.path, serde_json::to_vec(&*positions)?)?;
+        Ok(())
+    }
+
+    pub(crate) fn get(&self, label: &str) -> Option<Position> {
+        self.positions
+            .lock()
+            .unwrap()
+            .get(label)
+            .copied()
+            .and_then(Position::from_discriminant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(FILENAME);
+
+        let store = PositionStore::load(path.clone());
+        assert!(store.get("main").is_none());
+        store.save("main", &Position::BottomRight).unwrap();
+        store.save("settings", &Position::Center).unwrap();
+        store.save("popup", &Position::Cursor).unwrap();
+
+        let store = PositionStore::load(path);
+
+        assert!(matches!(store.get("main"), Some(Position::BottomRight)));
+        assert!(matches!(store.get("settings"), Some(Position::Center)));
+        assert!(store.get("popup").is_none());
+    }
+}
@@ -26,20 +26,141 @@ mod platform_impl;
 mod semver_compat;
 
 pub(crate) type SingleInstanceCallback<R> =
-    dyn FnMut(&AppHandle<R>, Vec<String>, String) + Send + Sync + 'static;
+    dyn FnMut(&AppHandle<R>, Vec<String>, String, usize) + Send + Sync + 'static;
+
+/// The slot claimed by this instance.
+pub(crate) struct Slot(pub(crate) usize);
 
 pub fn init<R: Runtime, F: FnMut(&AppHandle<R>, Vec<String>, String) + Send + Sync + 'static>(
     mut f: F,
 ) -> TauriPlugin<R> {
-    platform_impl::init(Box::new(move |app, args, cwd| {
-        #[cfg(feature = "deep-link")]
-        if let Some(deep_link) = app.try_state::<tauri_plugin_deep_link::DeepLink<R>>() {
-            deep_link.handle_cli_arguments(args.iter());
-        }
-        f(app, args, cwd)
-    }))
+    init_with_max_instances(1, move |app, args, cwd, _slot| f(app, args, cwd))
+}
+
+/// Same as [`init`], but lets up to `max_instances` instances of the app run at the same time.
+///
+/// Each instance claims the first free slot between `0` and `max_instances - 1`, with its own lock.
+/// Once all the slots are taken, new instances forward their arguments to the running instance
+/// with the lowest slot and exit. The callback receives the slot of the instance it runs in.
+pub fn init_with_max_instances<
+    R: Runtime,
+    F: FnMut(&AppHandle<R>, Vec<String>, String, usize) + Send + Sync + 'static,
+>(
+    max_instances: usize,
+    mut f: F,
+) -> TauriPlugin<R> {
+    platform_impl::init(
+        max_instances.max(1),
+        Box::new(move |app, args, cwd, slot| {
+            #[cfg(feature = "deep-link")]
+            if let Some(deep_link) = app.try_state::<tauri_plugin_deep_link::DeepLink<R>>() {
+                deep_link.handle_cli_arguments(args.iter());
+            }
+            f(app, args, cwd, slot)
+        }),
+    )
+}
+
+/// Returns the slot claimed by this instance, see [`init_with_max_instances`].
+///
+/// Returns `None` if the plugin is not registered or the instance could not claim a slot.
+pub fn instance_slot<R: Runtime, M: Manager<R>>(manager: &M) -> Option<usize> {
+    manager.try_state::<Slot>().map(|slot| slot.0)
 }
 
 pub fn destroy<R: Runtime, M: Manager<R>>(manager: &M) {
     platform_impl::destroy(manager)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::{Path, PathBuf},
+        process::{Child, Command, Stdio},
+        time::{Duration, Instant},
+    };
+
+    const TEST_ID_ENV: &str = "TAURI_SINGLE_INSTANCE_TEST_ID";
+    const TEST_SLOT_FILE_ENV: &str = "TAURI_SINGLE_INSTANCE_TEST_SLOT_FILE";
+
+    /// Claims a slot and holds it until stdin is closed, run in the child processes of
+    /// `bounds_instances_across_processes`.
+    #[test]
+    #[ignore]
+    fn hold_slot() {
+        let (Ok(id), Ok(slot_file)) = (
+            std::env::var(TEST_ID_ENV),
+            std::env::var(TEST_SLOT_FILE_ENV),
+        ) else {
+            return;
+        };
+        #[cfg(target_os = "linux")]
+        let slot = {
+            let callback: super::platform_impl::SharedCallback = std::sync::Arc::new(
+                std::sync::Mutex::new(|_: Vec<String>, _: String, _: usize| {}),
+            );
+            super::platform_impl::claim_slot(&id, 2, &callback).unwrap()
+        };
+        #[cfg(not(target_os = "linux"))]
+        let slot = super::platform_impl::claim_slot(&id, 2).unwrap();
+        let slot = format!("{:?}", slot.as_ref().map(|(slot, _)| *slot));
+
+        // write then rename so the parent never reads a partial file
+        let partial = PathBuf::from(format!("{slot_file}.partial"));
+        std::fs::write(&partial, slot).unwrap();
+        std::fs::rename(partial, slot_file).unwrap();
+
+        let _ = std::io::stdin().read_line(&mut String::new());
+    }
+
+    fn spawn_instance(id: &str, dir: &Path, name: &str) -> (Child, String) {
+        let slot_file = dir.join(name);
+        let child = Command::new(std::env::current_exe().unwrap())
+            .args(["tests::hold_slot", "--exact", "--ignored"])
+            .env(TEST_ID_ENV, id)
+            .env(TEST_SLOT_FILE_ENV, &slot_file)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(30);
+        let slot = loop {
+            if let Ok(slot) = std::fs::read_to_string(&slot_file) {
+                break slot;
+            }
+            assert!(Instant::now() < deadline, "{name} did not claim a slot");
+            std::thread::sleep(Duration::from_millis(20));
+        };
+        (child, slot)
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "linux",
+        ignore = "the instances claim their slots on the D-Bus session bus, run it with `dbus-run-session`"
+    )]
+    fn bounds_instances_across_processes() {
+        let id = format!("tauri_single_instance_test_{}", std::process::id());
+        let dir = tempfile::tempdir().unwrap();
+
+        let (mut first, slot) = spawn_instance(&id, dir.path(), "first");
+        assert_eq!(slot, "Some(0)");
+        let (mut second, slot) = spawn_instance(&id, dir.path(), "second");
+        assert_eq!(slot, "Some(1)");
+        let (mut third, slot) = spawn_instance(&id, dir.path(), "third");
+        assert_eq!(slot, "None");
+        third.wait().unwrap();
+
+        // closing stdin lets the instance exit, freeing its slot
+        drop(first.stdin.take());
+        first.wait().unwrap();
+        let (mut fourth, slot) = spawn_instance(&id, dir.path(), "fourth");
+        assert_eq!(slot, "Some(0)");
+
+        for child in [&mut second, &mut fourth] {
+            drop(child.stdin.take());
+            child.wait().unwrap();
+        }
+    }
+}
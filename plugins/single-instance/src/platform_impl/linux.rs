@@ -5,10 +5,12 @@
 #[cfg(feature = "semver")]
 use crate::semver_compat::semver_compat_string;
 
-use crate::SingleInstanceCallback;
+use std::sync::{Arc, Mutex};
+
+use crate::{SingleInstanceCallback, Slot};
 use tauri::{
     plugin::{self, TauriPlugin},
-    AppHandle, Config, Manager, RunEvent, Runtime,
+    Config, Manager, RunEvent, Runtime,
 };
 use zbus::{
     blocking::{connection::Builder, Connection},
@@ -17,15 +19,18 @@ use zbus::{
 
 struct ConnectionHandle(Connection);
 
-struct SingleInstanceDBus<R: Runtime> {
-    callback: Box<SingleInstanceCallback<R>>,
-    app_handle: AppHandle<R>,
+/// The callback shared by the objects served while looking for a free slot.
+pub(crate) type SharedCallback = Arc<Mutex<dyn FnMut(Vec<String>, String, usize) + Send>>;
+
+struct SingleInstanceDBus {
+    callback: SharedCallback,
+    slot: usize,
 }
 
 #[interface(name = "org.SingleInstance.DBus")]
-impl<R: Runtime> SingleInstanceDBus<R> {
+impl SingleInstanceDBus {
     fn execute_callback(&mut self, argv: Vec<String>, cwd: String) {
-        (self.callback)(&self.app_handle, argv, cwd);
+        (self.callback.lock().unwrap())(argv, cwd, self.slot);
     }
 }
 
@@ -42,54 +47,100 @@ fn dbus_id(config: &Config) -> String {
     config.identifier.replace(['.', '-'], "_")
 }
 
-pub fn init<R: Runtime>(f: Box<SingleInstanceCallback<R>>) -> TauriPlugin<R> {
+/// The bus name of `slot`, slot `0` keeps the name used before instance slots.
+fn dbus_name(id: &str, slot: usize) -> String {
+    if slot == 0 {
+        format!("org.{id}.SingleInstance")
+    } else {
+        format!("org.{id}.SingleInstance{slot}")
+    }
+}
+
+fn dbus_path(id: &str, slot: usize) -> String {
+    if slot == 0 {
+        format!("/org/{id}/SingleInstance")
+    } else {
+        format!("/org/{id}/SingleInstance{slot}")
+    }
+}
+
+/// Requests the bus name of the first free slot, returns `None` if all of them are taken.
+///
+/// The object calling `callback` is served before the name is requested,
+/// so an instance forwarding its arguments as soon as the slot is claimed finds it.
+pub(crate) fn claim_slot(
+    id: &str,
+    max_instances: usize,
+    callback: &SharedCallback,
+) -> zbus::Result<Option<(usize, Connection)>> {
+    for slot in 0..max_instances {
+        let single_instance_dbus = SingleInstanceDBus {
+            callback: callback.clone(),
+            slot,
+        };
+        match Builder::session()?
+            .serve_at(dbus_path(id, slot), single_instance_dbus)?
+            .name(dbus_name(id, slot))?
+            .replace_existing_names(false)
+            .allow_name_replacements(false)
+            .build()
+        {
+            Ok(connection) => return Ok(Some((slot, connection))),
+            Err(zbus::Error::NameTaken) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(None)
+}
+
+pub fn init<R: Runtime>(
+    max_instances: usize,
+    mut f: Box<SingleInstanceCallback<R>>,
+) -> TauriPlugin<R> {
     plugin::Builder::new("single-instance")
-        .setup(|app, _api| {
+        .setup(move |app, _api| {
             #[cfg(feature = "semver")]
             let id = dbus_id(app.config(), app.package_info().version.clone());
             #[cfg(not(feature = "semver"))]
             let id = dbus_id(app.config());
 
-            let single_instance_dbus = SingleInstanceDBus {
-                callback: f,
-                app_handle: app.clone(),
-            };
-            let dbus_name = format!("org.{id}.SingleInstance");
-            let dbus_path = format!("/org/{id}/SingleInstance");
-
-            match Builder::session()
-                .unwrap()
-                .name(dbus_name.as_str())
-                .unwrap()
-                .replace_existing_names(false)
-                .allow_name_replacements(false)
-                .serve_at(dbus_path.as_str(), single_instance_dbus)
-                .unwrap()
-                .build()
-            {
-                Ok(connection) => {
+            let app_handle = app.clone();
+            let callback: SharedCallback = Arc::new(Mutex::new(move |argv, cwd, slot| {
+                f(&app_handle, argv, cwd, slot)
+            }));
+
+            match claim_slot(&id, max_instances, &callback) {
+                Ok(Some((slot, connection))) => {
                     app.manage(ConnectionHandle(connection));
+                    app.manage(Slot(slot));
                 }
-                Err(zbus::Error::NameTaken) => {
+                Ok(None) => {
                     if let Ok(connection) = Connection::session() {
-                        let _ = connection.call_method(
-                            Some(dbus_name.as_str()),
-                            dbus_path.as_str(),
-                            Some("org.SingleInstance.DBus"),
-                            "ExecuteCallback",
-                            &(
-                                std::env::args().collect::<Vec<String>>(),
-                                std::env::current_dir()
-                                    .unwrap_or_default()
-                                    .to_str()
-                                    .unwrap_or_default(),
-                            ),
-                        );
+                        // forward to the running instance with the lowest slot
+                        let args = std::env::args().collect::<Vec<String>>();
+                        let cwd = std::env::current_dir().unwrap_or_default();
+                        let cwd = cwd.to_str().unwrap_or_default();
+                        for slot in 0..max_instances {
+                            let forwarded = connection.call_method(
+                                Some(dbus_name(&id, slot).as_str()),
+                                dbus_path(&id, slot).as_str(),
+                                Some("org.SingleInstance.DBus"),
+                                "ExecuteCallback",
+                                &(&args, cwd),
+                            );
+                            if forwarded.is_ok() {
+                                break;
+                            }
+                        }
                     }
                     app.cleanup_before_exit();
                     std::process::exit(0);
                 }
-                _ => {}
+                Err(e) => {
+                    tracing::error!(
+                        "single_instance failed to claim an instance slot - launching normally: {e}"
+                    );
+                }
             }
 
             Ok(())
@@ -103,7 +154,10 @@ pub fn init<R: Runtime>(f: Box<SingleInstanceCallback<R>>) -> TauriPlugin<R> {
 }
 
 pub fn destroy<R: Runtime, M: Manager<R>>(manager: &M) {
-    if let Some(connection) = manager.try_state::<ConnectionHandle>() {
+    if let (Some(connection), Some(slot)) = (
+        manager.try_state::<ConnectionHandle>(),
+        manager.try_state::<Slot>(),
+    ) {
         #[cfg(feature = "semver")]
         let id = dbus_id(
             manager.config(),
@@ -112,7 +166,6 @@ pub fn destroy<R: Runtime, M: Manager<R>>(manager: &M) {
         #[cfg(not(feature = "semver"))]
         let id = dbus_id(manager.config());
 
-        let dbus_name = format!("org.{id}.SingleInstance",);
-        let _ = connection.0.release_name(dbus_name);
+        let _ = connection.0.release_name(dbus_name(&id, slot.0));
     }
 }
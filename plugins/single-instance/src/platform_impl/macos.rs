@@ -10,37 +10,39 @@ use std::{
 
 #[cfg(feature = "semver")]
 use crate::semver_compat::semver_compat_string;
-use crate::SingleInstanceCallback;
+use crate::{SingleInstanceCallback, Slot};
 use tauri::{
     plugin::{self, TauriPlugin},
     AppHandle, Config, Manager, RunEvent, Runtime,
 };
 
-pub fn init<R: Runtime>(cb: Box<SingleInstanceCallback<R>>) -> TauriPlugin<R> {
+pub fn init<R: Runtime>(
+    max_instances: usize,
+    cb: Box<SingleInstanceCallback<R>>,
+) -> TauriPlugin<R> {
     plugin::Builder::new("single-instance")
-        .setup(|app, _api| {
-            let socket = socket_path(app.config(), app.package_info());
+        .setup(move |app, _api| {
+            let id = socket_id(app.config(), app.package_info());
 
-            // Notify the singleton which may or may not exist.
-            match notify_singleton(&socket) {
-                Ok(_) => {
-                    std::process::exit(0);
+            match claim_slot(&id, max_instances) {
+                Ok(Some((slot, listener))) => {
+                    app.manage(Slot(slot));
+                    listen_for_other_instances(listener, app.clone(), cb, slot);
                 }
-                Err(e) => {
-                    match e.kind() {
-                        ErrorKind::NotFound | ErrorKind::ConnectionRefused => {
-                            // This process claims itself as singleton as likely none exists
-                            socket_cleanup(&socket);
-                            listen_for_other_instances(&socket, app.clone(), cb);
-                        }
-                        _ => {
-                            tracing::debug!(
-                                "single_instance failed to notify - launching normally: {}",
-                                e
-                            );
-                        }
+                Ok(None) => {
+                    // Notify the running instance with the lowest slot.
+                    if (0..max_instances)
+                        .any(|slot| notify_singleton(&socket_path(&id, slot)).is_ok())
+                    {
+                        std::process::exit(0);
                     }
                 }
+                Err(e) => {
+                    tracing::debug!(
+                        "single_instance failed to notify - launching normally: {}",
+                        e
+                    );
+                }
             }
             Ok(())
         })
@@ -53,21 +55,52 @@ pub fn init<R: Runtime>(cb: Box<SingleInstanceCallback<R>>) -> TauriPlugin<R> {
 }
 
 pub fn destroy<R: Runtime, M: Manager<R>>(manager: &M) {
-    let socket = socket_path(manager.config(), manager.package_info());
-    socket_cleanup(&socket);
+    if let Some(slot) = manager.try_state::<Slot>() {
+        let id = socket_id(manager.config(), manager.package_info());
+        socket_cleanup(&socket_path(&id, slot.0));
+    }
 }
 
-fn socket_path(config: &Config, _package_info: &tauri::PackageInfo) -> PathBuf {
-    let identifier = config.identifier.replace(['.', '-'].as_ref(), "_");
-
+fn socket_id(config: &Config, _package_info: &tauri::PackageInfo) -> String {
+    #[allow(unused_mut)]
+    let mut identifier = config.identifier.replace(['.', '-'].as_ref(), "_");
     #[cfg(feature = "semver")]
-    let identifier = format!(
-        "{identifier}_{}",
-        semver_compat_string(_package_info.version.clone()),
-    );
+    {
+        identifier.push('_');
+        identifier.push_str(semver_compat_string(_package_info.version.clone()).as_str());
+    }
+    identifier
+}
 
+/// The socket of `slot`, slot `0` keeps the path used before instance slots.
+fn socket_path(id: &str, slot: usize) -> PathBuf {
     // Use /tmp as socket path must be shorter than 100 chars.
-    PathBuf::from(format!("/tmp/{}_si.sock", identifier))
+    if slot == 0 {
+        PathBuf::from(format!("/tmp/{id}_si.sock"))
+    } else {
+        PathBuf::from(format!("/tmp/{id}_si{slot}.sock"))
+    }
+}
+
+/// Listens on the socket of the first free slot, returns `None` if all of them are taken.
+pub(crate) fn claim_slot(
+    id: &str,
+    max_instances: usize,
+) -> Result<Option<(usize, UnixListener)>, Error> {
+    for slot in 0..max_instances {
+        let socket = socket_path(id, slot);
+        // An instance listens on the socket, it ignores the empty connection.
+        match UnixStream::connect(&socket) {
+            Ok(_) => {}
+            Err(e) if matches!(e.kind(), ErrorKind::NotFound | ErrorKind::ConnectionRefused) => {
+                // This process claims the slot as likely no instance uses it
+                socket_cleanup(&socket);
+                return UnixListener::bind(&socket).map(|listener| Some((slot, listener)));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(None)
 }
 
 fn socket_cleanup(socket: &PathBuf) {
@@ -92,42 +125,34 @@ fn notify_singleton(socket: &PathBuf) -> Result<(), Error> {
 }
 
 fn listen_for_other_instances<A: Runtime>(
-    socket: &PathBuf,
+    listener: UnixListener,
     app: AppHandle<A>,
     mut cb: Box<SingleInstanceCallback<A>>,
+    slot: usize,
 ) {
-    match UnixListener::bind(socket) {
-        Ok(listener) => {
-            tauri::async_runtime::spawn(async move {
-                for stream in listener.incoming() {
-                    match stream {
-                        Ok(mut stream) => {
-                            let mut s = String::new();
-                            match stream.read_to_string(&mut s) {
-                                Ok(_) => {
-                                    let (cwd, args) = s.split_once("\0\0").unwrap_or_default();
-                                    let args: Vec<String> =
-                                        args.split('\0').map(String::from).collect();
-                                    cb(app.app_handle(), args, cwd.to_string());
-                                }
-                                Err(e) => {
-                                    tracing::debug!("single_instance failed to be notified: {e}")
-                                }
-                            }
+    tauri::async_runtime::spawn(async move {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(mut stream) => {
+                    let mut s = String::new();
+                    match stream.read_to_string(&mut s) {
+                        // an instance probing for a free slot
+                        Ok(0) => {}
+                        Ok(_) => {
+                            let (cwd, args) = s.split_once("\0\0").unwrap_or_default();
+                            let args: Vec<String> = args.split('\0').map(String::from).collect();
+                            cb(app.app_handle(), args, cwd.to_string(), slot);
                         }
-                        Err(err) => {
-                            tracing::debug!("single_instance failed to be notified: {}", err);
-                            continue;
+                        Err(e) => {
+                            tracing::debug!("single_instance failed to be notified: {e}")
                         }
                     }
                 }
-            });
-        }
-        Err(err) => {
-            tracing::error!(
-                "single_instance failed to listen to other processes - launching normally: {}",
-                err
-            );
+                Err(err) => {
+                    tracing::debug!("single_instance failed to be notified: {}", err);
+                    continue;
+                }
+            }
         }
-    }
+    });
 }
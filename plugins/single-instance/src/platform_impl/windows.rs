@@ -5,7 +5,7 @@
 #[cfg(feature = "semver")]
 use crate::semver_compat::semver_compat_string;
 
-use crate::SingleInstanceCallback;
+use crate::{SingleInstanceCallback, Slot};
 use std::ffi::CStr;
 use tauri::{
     plugin::{self, TauriPlugin},
@@ -28,13 +28,14 @@ use windows_sys::Win32::{
 
 const WMCOPYDATA_SINGLE_INSTANCE_DATA: usize = 1542;
 
-struct MutexHandle(isize);
+pub(crate) struct MutexHandle(isize);
 
 struct TargetWindowHandle(isize);
 
 struct UserData<R: Runtime> {
     app: AppHandle<R>,
     callback: Box<SingleInstanceCallback<R>>,
+    slot: usize,
 }
 
 impl<R: Runtime> UserData<R> {
@@ -47,13 +48,16 @@ impl<R: Runtime> UserData<R> {
     }
 
     fn run_callback(&mut self, args: Vec<String>, cwd: String) {
-        (self.callback)(&self.app, args, cwd)
+        (self.callback)(&self.app, args, cwd, self.slot)
     }
 }
 
-pub fn init<R: Runtime>(callback: Box<SingleInstanceCallback<R>>) -> TauriPlugin<R> {
+pub fn init<R: Runtime>(
+    max_instances: usize,
+    callback: Box<SingleInstanceCallback<R>>,
+) -> TauriPlugin<R> {
     plugin::Builder::new("single-instance")
-        .setup(|app, _api| {
+        .setup(move |app, _api| {
             #[allow(unused_mut)]
             let mut id = app.config().identifier.clone();
             #[cfg(feature = "semver")]
@@ -62,48 +66,57 @@ pub fn init<R: Runtime>(callback: Box<SingleInstanceCallback<R>>) -> TauriPlugin
                 id.push_str(semver_compat_string(app.package_info().version.clone()).as_str());
             }
 
-            let class_name = encode_wide(format!("{id}-sic"));
-            let window_name = encode_wide(format!("{id}-siw"));
-            let mutex_name = encode_wide(format!("{id}-sim"));
-
-            let hmutex =
-                unsafe { CreateMutexW(std::ptr::null(), true.into(), mutex_name.as_ptr()) };
-
-            if unsafe { GetLastError() } == ERROR_ALREADY_EXISTS {
-                unsafe {
-                    let hwnd = FindWindowW(class_name.as_ptr(), window_name.as_ptr());
-
-                    if !hwnd.is_null() {
-                        let cwd = std::env::current_dir().unwrap_or_default();
-                        let cwd = cwd.to_str().unwrap_or_default();
-
-                        let args = std::env::args().collect::<Vec<String>>().join("|");
-
-                        let data = format!("{cwd}|{args}\0",);
-
-                        let bytes = data.as_bytes();
-                        let cds = COPYDATASTRUCT {
-                            dwData: WMCOPYDATA_SINGLE_INSTANCE_DATA,
-                            cbData: bytes.len() as _,
-                            lpData: bytes.as_ptr() as _,
-                        };
-
-                        SendMessageW(hwnd, WM_COPYDATA, 0, &cds as *const _ as _);
-
-                        app.cleanup_before_exit();
-                        std::process::exit(0);
+            match claim_slot(&id, max_instances) {
+                Ok(Some((slot, hmutex))) => {
+                    app.manage(hmutex);
+                    app.manage(Slot(slot));
+
+                    let class_name = encode_wide(slot_name(&id, "sic", slot));
+                    let window_name = encode_wide(slot_name(&id, "siw", slot));
+                    let userdata = UserData {
+                        app: app.clone(),
+                        callback,
+                        slot,
+                    };
+                    let userdata = Box::into_raw(Box::new(userdata));
+                    let hwnd = create_event_target_window::<R>(&class_name, &window_name, userdata);
+                    app.manage(TargetWindowHandle(hwnd as _));
+                }
+                Ok(None) => {
+                    // forward to the running instance with the lowest slot
+                    for slot in 0..max_instances {
+                        let class_name = encode_wide(slot_name(&id, "sic", slot));
+                        let window_name = encode_wide(slot_name(&id, "siw", slot));
+                        let hwnd =
+                            unsafe { FindWindowW(class_name.as_ptr(), window_name.as_ptr()) };
+
+                        if !hwnd.is_null() {
+                            let cwd = std::env::current_dir().unwrap_or_default();
+                            let cwd = cwd.to_str().unwrap_or_default();
+
+                            let args = std::env::args().collect::<Vec<String>>().join("|");
+
+                            let data = format!("{cwd}|{args}\0",);
+
+                            let bytes = data.as_bytes();
+                            let cds = COPYDATASTRUCT {
+                                dwData: WMCOPYDATA_SINGLE_INSTANCE_DATA,
+                                cbData: bytes.len() as _,
+                                lpData: bytes.as_ptr() as _,
+                            };
+
+                            unsafe { SendMessageW(hwnd, WM_COPYDATA, 0, &cds as *const _ as _) };
+
+                            app.cleanup_before_exit();
+                            std::process::exit(0);
+                        }
                     }
                 }
-            } else {
-                app.manage(MutexHandle(hmutex as _));
-
-                let userdata = UserData {
-                    app: app.clone(),
-                    callback,
-                };
-                let userdata = Box::into_raw(Box::new(userdata));
-                let hwnd = create_event_target_window::<R>(&class_name, &window_name, userdata);
-                app.manage(TargetWindowHandle(hwnd as _));
+                Err(e) => {
+                    tracing::error!(
+                        "single_instance failed to claim an instance slot - launching normally: {e}"
+                    );
+                }
             }
 
             Ok(())
@@ -116,6 +129,36 @@ pub fn init<R: Runtime>(callback: Box<SingleInstanceCallback<R>>) -> TauriPlugin
         .build()
 }
 
+/// The name of the `kind` object of `slot`, slot `0` keeps the names used before instance slots.
+fn slot_name(id: &str, kind: &str, slot: usize) -> String {
+    if slot == 0 {
+        format!("{id}-{kind}")
+    } else {
+        format!("{id}-{kind}{slot}")
+    }
+}
+
+/// Creates the mutex of the first free slot, returns `None` if all of them are taken.
+pub(crate) fn claim_slot(
+    id: &str,
+    max_instances: usize,
+) -> std::io::Result<Option<(usize, MutexHandle)>> {
+    for slot in 0..max_instances {
+        let mutex_name = encode_wide(slot_name(id, "sim", slot));
+        let hmutex = unsafe { CreateMutexW(std::ptr::null(), true.into(), mutex_name.as_ptr()) };
+        if hmutex.is_null() {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        if unsafe { GetLastError() } == ERROR_ALREADY_EXISTS {
+            unsafe { CloseHandle(hmutex) };
+        } else {
+            return Ok(Some((slot, MutexHandle(hmutex as _))));
+        }
+    }
+    Ok(None)
+}
+
 pub fn destroy<R: Runtime, M: Manager<R>>(manager: &M) {
     if let Some(hmutex) = manager.try_state::<MutexHandle>() {
         unsafe {
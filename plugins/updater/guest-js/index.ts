@@ -46,6 +46,7 @@ interface UpdateMetadata {
   version: string
   date?: string
   body?: string
+  minimumSystemVersion?: string
   rawJson: Record<string, unknown>
 }
 
@@ -55,6 +56,23 @@ type DownloadEvent =
   | { event: 'Progress'; data: { chunkLength: number } }
   | { event: 'Finished' }
 
+/**
+ * A downloaded and verified updater package, kept by the app until it is installed or closed.
+ *
+ * The package outlives the webview that downloaded it, so it can be installed later,
+ * e.g. before the app quits, by creating a {@linkcode DownloadedUpdate} from its `rid`.
+ *
+ * @since 2.10.0
+ */
+class DownloadedUpdate extends Resource {
+  /** Install the downloaded updater package, the resource is closed once installed */
+  async install(): Promise<void> {
+    await invoke('plugin:updater|install', {
+      bytesRid: this.rid
+    })
+  }
+}
+
 class Update extends Resource {
   // TODO: remove this field in v3
   /** @deprecated This is always true, check if the return value is `null` instead when using {@linkcode check} */
@@ -63,8 +81,14 @@ class Update extends Resource {
   version: string
   date?: string
   body?: string
+  /**
+   * Minimum operating system version the update runs on.
+   *
+   * @since 2.10.0
+   */
+  minimumSystemVersion?: string
   rawJson: Record<string, unknown>
-  private downloadedBytes?: Resource
+  private downloadedBytes?: DownloadedUpdate
 
   constructor(metadata: UpdateMetadata) {
     super(metadata.rid)
@@ -73,14 +97,20 @@ class Update extends Resource {
     this.version = metadata.version
     this.date = metadata.date
     this.body = metadata.body
+    this.minimumSystemVersion = metadata.minimumSystemVersion
     this.rawJson = metadata.rawJson
   }
 
-  /** Download the updater package */
+  /**
+   * Download the updater package
+   *
+   * Resolves to the downloaded package, which can be installed later with {@linkcode Update.install}
+   * or {@linkcode DownloadedUpdate.install}.
+   */
   async download(
     onEvent?: (progress: DownloadEvent) => void,
     options?: DownloadOptions
-  ): Promise<void> {
+  ): Promise<DownloadedUpdate> {
     convertToRustHeaders(options)
     const channel = new Channel<DownloadEvent>()
     if (onEvent) {
@@ -91,7 +121,8 @@ class Update extends Resource {
       rid: this.rid,
       ...options
     })
-    this.downloadedBytes = new Resource(downloadedBytesRid)
+    this.downloadedBytes = new DownloadedUpdate(downloadedBytesRid)
+    return this.downloadedBytes
   }
 
   /** Install downloaded updater package */
@@ -100,10 +131,7 @@ class Update extends Resource {
       throw new Error('Update.install called before Update.download')
     }
 
-    await invoke('plugin:updater|install', {
-      updateRid: this.rid,
-      bytesRid: this.downloadedBytes.rid
-    })
+    await this.downloadedBytes.install()
 
     // Don't need to call close, we did it in rust side already
     this.downloadedBytes = undefined
@@ -152,4 +180,4 @@ function convertToRustHeaders(options?: { headers?: HeadersInit }) {
 }
 
 export type { CheckOptions, DownloadOptions, DownloadEvent }
-export { check, Update }
+export { check, Update, DownloadedUpdate }
@@ -33,11 +33,23 @@ pub(crate) struct Metadata {
     version: String,
     date: Option<String>,
     body: Option<String>,
+    minimum_system_version: Option<String>,
     raw_json: serde_json::Value,
 }
 
-struct DownloadedBytes(pub Vec<u8>);
-impl Resource for DownloadedBytes {}
+/// A verified update package, kept in the app resources table so it can be installed
+/// later from any webview for as long as the app runs.
+struct DownloadedUpdate {
+    update: Update,
+    bytes: Vec<u8>,
+}
+impl Resource for DownloadedUpdate {}
+
+impl DownloadedUpdate {
+    fn install(&self) -> Result<()> {
+        self.update.install(&self.bytes)
+    }
+}
 
 #[tauri::command]
 pub(crate) async fn check<R: Runtime>(
@@ -85,6 +97,7 @@ pub(crate) async fn check<R: Runtime>(
             version: update.version.clone(),
             date: formatted_date,
             body: update.body.clone(),
+            minimum_system_version: update.minimum_system_version.clone(),
             raw_json: update.raw_json.clone(),
             rid: webview.resources_table().add(update),
         };
@@ -134,21 +147,20 @@ pub(crate) async fn download<R: Runtime>(
         )
         .await?;
 
-    Ok(webview.resources_table().add(DownloadedBytes(bytes)))
+    Ok(webview
+        .app_handle()
+        .resources_table()
+        .add(DownloadedUpdate { update, bytes }))
 }
 
 #[tauri::command]
-pub(crate) async fn install<R: Runtime>(
-    webview: Webview<R>,
-    update_rid: ResourceId,
-    bytes_rid: ResourceId,
-) -> Result<()> {
-    let update = webview.resources_table().get::<Update>(update_rid)?;
-    let bytes = webview
+pub(crate) async fn install<R: Runtime>(webview: Webview<R>, bytes_rid: ResourceId) -> Result<()> {
+    let downloaded = webview
+        .app_handle()
         .resources_table()
-        .get::<DownloadedBytes>(bytes_rid)?;
-    update.install(&bytes.0)?;
-    let _ = webview.resources_table().close(bytes_rid);
+        .get::<DownloadedUpdate>(bytes_rid)?;
+    downloaded.install()?;
+    let _ = webview.app_handle().resources_table().close(bytes_rid);
     Ok(())
 }
 
@@ -195,3 +207,43 @@ pub(crate) async fn download_and_install<R: Runtime>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::updater::tests::{on_before_install, test_update, TEST_PAYLOAD};
+    use std::sync::{Arc, Mutex};
+    use tauri::ResourceTable;
+
+    #[tokio::test]
+    async fn installs_from_the_stored_download() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/update")
+            .with_status(200)
+            .with_body(TEST_PAYLOAD)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let installed = Arc::new(Mutex::new(Vec::new()));
+        let installed_ = installed.clone();
+        let update = on_before_install(
+            test_update(
+                format!("{}/update", server.url()).parse().unwrap(),
+                Vec::new(),
+            ),
+            move |bytes| installed_.lock().unwrap().extend_from_slice(bytes),
+        );
+
+        let mut resources = ResourceTable::default();
+        let bytes = update.download(|_, _| {}, || {}).await.unwrap();
+        let rid = resources.add(DownloadedUpdate { update, bytes });
+
+        // the payload is not a real installer, so only the hook matters here
+        let _ = resources.get::<DownloadedUpdate>(rid).unwrap().install();
+
+        mock.assert_async().await;
+        assert_eq!(*installed.lock().unwrap(), TEST_PAYLOAD);
+    }
+}
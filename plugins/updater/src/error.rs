@@ -21,6 +21,13 @@ pub enum Error {
     /// Serialization errors.
     #[error(transparent)]
     Serialization(#[from] serde_json::Error),
+    /// The version of the release JSON is not a valid semver version.
+    #[error("The `{field}` field of the release JSON is not a valid semver version: `{version}` ({source})")]
+    InvalidReleaseVersion {
+        field: &'static str,
+        version: String,
+        source: semver::Error,
+    },
     /// Could not fetch a valid response from the server.
     #[error("Could not fetch a valid release JSON from the remote")]
     ReleaseNotFound,
@@ -70,6 +77,9 @@ pub enum Error {
     BinaryNotFoundInArchive,
     #[error("failed to create temporary directory")]
     TempDirNotFound,
+    /// The temp directory configured with [`UpdaterBuilder::temp_dir`](crate::UpdaterBuilder::temp_dir) can't be written to.
+    #[error("temp directory {0} is not writable")]
+    TempDirNotWritable(std::path::PathBuf),
     #[error("Authentication failed or was cancelled")]
     AuthenticationFailed,
     #[error("Failed to install .deb package")]
@@ -89,6 +99,27 @@ pub enum Error {
     /// The configured updater endpoint must use a secure protocol like `https`
     #[error("The configured updater endpoint must use a secure protocol like `https`.")]
     InsecureTransportProtocol,
+    #[error("The update was cancelled")]
+    Cancelled,
+    /// The [`before_check`](crate::UpdaterBuilder::before_check) hook vetoed the update check.
+    #[error("The update check was vetoed by the `before_check` hook")]
+    CheckVetoed,
+    /// The update manifest has no signature while [`verify_manifest`](crate::UpdaterBuilder::verify_manifest) is enabled.
+    #[error(
+        "The update manifest is not signed, its signature must be sent in the `{}` header",
+        crate::MANIFEST_SIGNATURE_HEADER
+    )]
+    ManifestSignatureMissing,
+    /// The update endpoint responded with a content type that is not [accepted](crate::UpdaterBuilder::accepted_content_types),
+    /// e.g. the HTML error page of a misconfigured CDN.
+    #[error(
+        "The update endpoint responded with an unexpected content type `{content_type}`: {snippet}"
+    )]
+    UnexpectedContentType {
+        content_type: String,
+        /// The start of the response body.
+        snippet: String,
+    },
     #[error(transparent)]
     Tauri(#[from] tauri::Error),
 }
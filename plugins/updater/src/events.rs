@@ -0,0 +1,180 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The events emitted to all the webviews by default while checking for, downloading and installing an update,
+//! so any window can show the update status.
+//!
+//! See [`UpdaterBuilder::emit_events`](crate::UpdaterBuilder::emit_events) and
+//! [`UpdaterBuilder::events_target`](crate::UpdaterBuilder::events_target) to turn them off or send them to some webviews only.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tauri::EventTarget;
+
+/// Emitted when [`Updater::check`](crate::Updater::check) starts, without payload.
+pub const CHECKING: &str = "updater://checking";
+/// Emitted when the check found an update, with an [`UpdateAvailable`] payload.
+pub const UPDATE_AVAILABLE: &str = "updater://update-available";
+/// Emitted when the check found no update, without payload.
+pub const NO_UPDATE: &str = "updater://no-update";
+/// Emitted while the package is [downloaded](crate::Update::download), with a [`DownloadProgress`] payload,
+/// at most once every [`DOWNLOAD_PROGRESS_INTERVAL`].
+pub const DOWNLOAD_PROGRESS: &str = "updater://download-progress";
+/// Emitted once the package is downloaded, before it is verified, without payload.
+pub const DOWNLOAD_FINISHED: &str = "updater://download-finished";
+/// Emitted when the package starts [installing](crate::Update::install), without payload.
+pub const INSTALLING: &str = "updater://installing";
+/// Emitted when checking, downloading or installing fails, with an [`UpdaterError`] payload.
+pub const ERROR: &str = "updater://error";
+
+/// The minimum time between two [`DOWNLOAD_PROGRESS`] events, so a fast download doesn't flood the webviews.
+pub const DOWNLOAD_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Payload of the [`UPDATE_AVAILABLE`] event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAvailable {
+    pub current_version: String,
+    pub version: String,
+    /// The publish date of the update, formatted as RFC 3339.
+    pub date: Option<String>,
+    pub body: Option<String>,
+}
+
+/// Payload of the [`DOWNLOAD_PROGRESS`] event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProgress {
+    /// Number of bytes downloaded since the previous event.
+    pub chunk_length: usize,
+    /// Number of bytes downloaded so far.
+    pub downloaded: u64,
+    /// Size of the package, if known.
+    pub content_length: Option<u64>,
+}
+
+/// Payload of the [`ERROR`] event.
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdaterError {
+    pub message: String,
+}
+
+type EmitFn = dyn Fn(&EventTarget, &'static str, serde_json::Value) + Send + Sync;
+
+/// Emits the update events to a target, does nothing when created with [`Default`].
+#[derive(Clone, Default)]
+pub(crate) struct EventEmitter(Option<(Arc<EmitFn>, EventTarget)>);
+
+impl EventEmitter {
+    pub(crate) fn new<
+        F: Fn(&EventTarget, &'static str, serde_json::Value) + Send + Sync + 'static,
+    >(
+        f: F,
+    ) -> Self {
+        Self(Some((Arc::new(f), EventTarget::Any)))
+    }
+
+    /// Emits the events to `target` instead.
+    pub(crate) fn to(self, target: EventTarget) -> Self {
+        Self(self.0.map(|(emit, _)| (emit, target)))
+    }
+
+    pub(crate) fn emit(&self, event: &'static str, payload: impl Serialize) {
+        let Some((emit, target)) = &self.0 else {
+            return;
+        };
+        match serde_json::to_value(payload) {
+            Ok(payload) => emit(target, event, payload),
+            Err(err) => log::error!("failed to serialize the {event} event payload: {err}"),
+        }
+    }
+
+    pub(crate) fn emit_error(&self, error: &crate::Error) {
+        self.emit(
+            ERROR,
+            UpdaterError {
+                message: error.to_string(),
+            },
+        );
+    }
+}
+
+/// Gathers the downloaded chunks into [`DOWNLOAD_PROGRESS`] events sent at most once every [`DOWNLOAD_PROGRESS_INTERVAL`].
+#[derive(Debug, Default)]
+pub(crate) struct ProgressThrottle {
+    downloaded: u64,
+    content_length: Option<u64>,
+    pending: usize,
+    last_emitted: Option<Instant>,
+}
+
+impl ProgressThrottle {
+    /// Records a chunk downloaded at `now`, returning the progress to emit if it's time to.
+    pub(crate) fn chunk(
+        &mut self,
+        chunk_length: usize,
+        content_length: Option<u64>,
+        now: Instant,
+    ) -> Option<DownloadProgress> {
+        self.downloaded += chunk_length as u64;
+        self.content_length = content_length;
+        self.pending += chunk_length;
+        // the last chunk is always reported
+        let due = content_length == Some(self.downloaded)
+            || self.last_emitted.map_or(true, |last| {
+                now.duration_since(last) >= DOWNLOAD_PROGRESS_INTERVAL
+            });
+        if !due {
+            return None;
+        }
+        self.last_emitted = Some(now);
+        Some(self.progress())
+    }
+
+    /// Returns the progress not emitted yet once the download finished.
+    pub(crate) fn flush(&mut self) -> Option<DownloadProgress> {
+        (self.pending > 0).then(|| self.progress())
+    }
+
+    fn progress(&mut self) -> DownloadProgress {
+        DownloadProgress {
+            chunk_length: std::mem::take(&mut self.pending),
+            downloaded: self.downloaded,
+            content_length: self.content_length,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttles_download_progress() {
+        let mut throttle = ProgressThrottle::default();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        // the first chunk is reported right away, the next ones once the interval elapsed
+        assert_eq!(throttle.chunk(10, Some(100), at(0)).unwrap().downloaded, 10);
+        assert!(throttle.chunk(10, Some(100), at(50)).is_none());
+        let progress = throttle.chunk(10, Some(100), at(100)).unwrap();
+        assert_eq!((progress.chunk_length, progress.downloaded), (20, 30));
+
+        // and the last one regardless of the interval
+        assert!(throttle.chunk(70, Some(100), at(110)).is_some());
+        assert!(throttle.flush().is_none());
+
+        // without a content length, the remaining progress is flushed at the end
+        let mut throttle = ProgressThrottle::default();
+        throttle.chunk(10, None, at(0));
+        assert!(throttle.chunk(10, None, at(10)).is_none());
+        let progress = throttle.flush().unwrap();
+        assert_eq!((progress.chunk_length, progress.downloaded), (10, 20));
+    }
+}
@@ -23,10 +23,12 @@ use tauri::{
 mod commands;
 mod config;
 mod error;
+pub mod events;
 mod updater;
 
 pub use config::Config;
 pub use error::{Error, Result};
+pub use tokio_util::sync::CancellationToken;
 pub use updater::*;
 
 /// Extensions to [`tauri::App`], [`tauri::AppHandle`], [`tauri::WebviewWindow`], [`tauri::Webview`] and [`tauri::Window`] to access the updater APIs.
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-const COMMANDS: &[&str] = &["download", "upload"];
+const COMMANDS: &[&str] = &["download", "upload", "upload_dir_zip"];
 
 fn main() {
     tauri_plugin::Builder::new(COMMANDS)
@@ -37,17 +37,51 @@ async function upload(
   })
 }
 
-/// Download file from given url.
+/// Upload a directory to the given url as a zip archive.
 ///
-/// Note that `filePath` currently must include the file name.
+/// The archive is streamed while the directory is read, so it is never written to disk.
+/// Empty directories are preserved and symbolic links are only archived when they point to a file inside `dirPath`.
+///
+/// `dirPath` and everything in it must be allowed by the scope of the fs plugin, and the
+/// `upload:allow-upload-dir-zip` permission is not part of the default permission set.
+async function uploadDirZip(
+  url: string,
+  dirPath: string,
+  progressHandler?: ProgressHandler,
+  headers?: Map<string, string>
+): Promise<string> {
+  const ids = new Uint32Array(1)
+  window.crypto.getRandomValues(ids)
+  const id = ids[0]
+
+  const onProgress = new Channel<ProgressPayload>()
+  if (progressHandler) {
+    onProgress.onmessage = progressHandler
+  }
+
+  return await invoke('plugin:upload|upload_dir_zip', {
+    id,
+    url,
+    dirPath,
+    headers: headers ?? {},
+    onProgress
+  })
+}
+
+/// Download file from given url, returning the path of the downloaded file.
+///
+/// Note that `filePath` must include the file name, unless `useContentDisposition` is set
+/// and `filePath` is a directory: the file is then named after the response's `Content-Disposition` header,
+/// falling back to the last segment of the URL. Path separators are stripped from the suggested name.
 /// Furthermore the progress events will report a total length of 0 if the server did not sent a `Content-Length` header or if the file is compressed.
 async function download(
   url: string,
   filePath: string,
   progressHandler?: ProgressHandler,
   headers?: Map<string, string>,
-  body?: string
-): Promise<void> {
+  body?: string,
+  useContentDisposition?: boolean
+): Promise<string> {
   const ids = new Uint32Array(1)
   window.crypto.getRandomValues(ids)
   const id = ids[0]
@@ -57,14 +91,15 @@ async function download(
     onProgress.onmessage = progressHandler
   }
 
-  await invoke('plugin:upload|download', {
+  return await invoke('plugin:upload|download', {
     id,
     url,
     filePath,
     headers: headers ?? {},
     onProgress,
-    body
+    body,
+    useContentDisposition
   })
 }
 
-export { download, upload }
+export { download, upload, uploadDirZip }
@@ -0,0 +1,167 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Derives the name of a downloaded file from the response.
+
+use reqwest::{header::CONTENT_DISPOSITION, Response};
+
+/// The name suggested by the `Content-Disposition` header, else the last segment of the URL.
+///
+/// The name is sanitized so it can't point outside of the directory it is joined to.
+pub fn from_response(response: &Response) -> Option<String> {
+    response
+        .headers()
+        .get(CONTENT_DISPOSITION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(from_content_disposition)
+        .and_then(|name| sanitize(&name))
+        .or_else(|| {
+            let segment = response.url().path_segments()?.next_back()?;
+            sanitize(&percent_decode(segment)?)
+        })
+}
+
+/// Extracts the `filename*` parameter of a `Content-Disposition` header value, else its `filename` parameter.
+fn from_content_disposition(value: &str) -> Option<String> {
+    let mut filename = None;
+    for param in split_params(value).into_iter().skip(1) {
+        let Some((name, value)) = param.split_once('=') else {
+            continue;
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            // RFC 5987 encoded, e.g. `UTF-8''na%C3%AFve.txt`
+            "filename*" => {
+                let mut parts = value.trim().splitn(3, '\'');
+                let (Some(charset), Some(_language), Some(encoded)) =
+                    (parts.next(), parts.next(), parts.next())
+                else {
+                    continue;
+                };
+                if charset.eq_ignore_ascii_case("utf-8") {
+                    if let Some(decoded) = percent_decode(encoded) {
+                        return Some(decoded);
+                    }
+                }
+            }
+            "filename" => filename = Some(unquote(value.trim())),
+            _ => {}
+        }
+    }
+    filename
+}
+
+/// Splits a header value on the `;` that are not in a quoted string.
+fn split_params(value: &str) -> Vec<&str> {
+    let mut params = Vec::new();
+    let (mut start, mut quoted, mut escaped) = (0, false, false);
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                params.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    params.push(&value[start..]);
+    params
+}
+
+fn unquote(value: &str) -> String {
+    let Some(value) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    else {
+        return value.to_string();
+    };
+    let mut unquoted = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unquoted.extend(chars.next()),
+            c => unquoted.push(c),
+        }
+    }
+    unquoted
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = value.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Keeps the last component of `name` and replaces the characters not allowed in file names,
+/// `None` if nothing usable is left.
+fn sanitize(name: &str) -> Option<String> {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    // Windows drops trailing dots and spaces, which would also turn `..` into the parent directory
+    let name = name.trim().trim_end_matches(['.', ' ']);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_content_disposition() {
+        assert_eq!(
+            from_content_disposition(r#"attachment; filename="report; final.pdf""#).as_deref(),
+            Some("report; final.pdf")
+        );
+        assert_eq!(
+            from_content_disposition(r#"attachment; filename="a \"quoted\" name.txt""#).as_deref(),
+            Some(r#"a "quoted" name.txt"#)
+        );
+        assert_eq!(
+            from_content_disposition("attachment; filename=plain.txt").as_deref(),
+            Some("plain.txt")
+        );
+        // the extended parameter wins, wherever it is
+        assert_eq!(
+            from_content_disposition(
+                r#"attachment; filename*=UTF-8''r%C3%A9sum%C3%A9.txt; filename="resume.txt""#
+            )
+            .as_deref(),
+            Some("résumé.txt")
+        );
+        assert_eq!(from_content_disposition("inline"), None);
+    }
+
+    #[test]
+    fn sanitizes_file_names() {
+        assert_eq!(sanitize("../../etc/passwd").as_deref(), Some("passwd"));
+        assert_eq!(
+            sanitize(r"..\..\Windows\win.ini").as_deref(),
+            Some("win.ini")
+        );
+        assert_eq!(sanitize("what?.txt").as_deref(), Some("what_.txt"));
+        assert_eq!(sanitize(".."), None);
+        assert_eq!(sanitize("dir/"), None);
+        assert_eq!(sanitize(" . "), None);
+    }
+}
@@ -11,8 +11,11 @@
     html_favicon_url = "https://github.com/tauri-apps/tauri/raw/dev/app-icon.png"
 )]
 
+mod file_name;
 mod transfer_stats;
+mod zip_stream;
 use transfer_stats::TransferStats;
+use zip_stream::ZipDir;
 
 use futures_util::TryStreamExt;
 use serde::{ser::Serializer, Serialize};
@@ -20,7 +23,7 @@ use tauri::{
     command,
     ipc::Channel,
     plugin::{Builder as PluginBuilder, TauriPlugin},
-    Runtime,
+    Runtime, Webview,
 };
 use tokio::{
     fs::File,
@@ -30,7 +33,10 @@ use tokio_util::codec::{BytesCodec, FramedRead};
 
 use read_progress_stream::ReadProgressStream;
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -44,6 +50,12 @@ pub enum Error {
     ContentLength(String),
     #[error("request failed with status code {0}: {1}")]
     HttpErrorCode(u16, String),
+    #[error("cannot stream directory as zip: {0}")]
+    Zip(String),
+    #[error("path not allowed on the configured fs scope: {0}")]
+    PathNotAllowed(PathBuf),
+    #[error("cannot derive a file name from the response of {0}")]
+    FileName(String),
 }
 
 impl Serialize for Error {
@@ -71,7 +83,8 @@ async fn download(
     headers: HashMap<String, String>,
     body: Option<String>,
     on_progress: Channel<ProgressPayload>,
-) -> Result<()> {
+    use_content_disposition: Option<bool>,
+) -> Result<String> {
     tokio::spawn(async move {
         let client = reqwest::Client::new();
         let mut request = if let Some(body) = body {
@@ -94,6 +107,13 @@ async fn download(
         }
         let total = response.content_length().unwrap_or(0);
 
+        let mut file_path = PathBuf::from(file_path);
+        if use_content_disposition.unwrap_or_default() && file_path.is_dir() {
+            let file_name =
+                file_name::from_response(&response).ok_or_else(|| Error::FileName(url.clone()))?;
+            file_path.push(file_name);
+        }
+
         let mut file = BufWriter::new(File::create(&file_path).await?);
         let mut stream = response.bytes_stream();
 
@@ -109,7 +129,7 @@ async fn download(
             });
         }
         file.flush().await?;
-        Ok(())
+        Ok(file_path.to_string_lossy().into_owned())
     })
     .await
     .map_err(|e| Error::Io(std::io::Error::other(e.to_string())))?
@@ -154,9 +174,78 @@ async fn upload(
     .map_err(|e| Error::Io(std::io::Error::other(e.to_string())))?
 }
 
+#[command]
+async fn upload_dir_zip<R: Runtime>(
+    webview: Webview<R>,
+    url: String,
+    dir_path: String,
+    headers: HashMap<String, String>,
+    on_progress: Channel<ProgressPayload>,
+) -> Result<String> {
+    let fs_scope = tauri_plugin_fs::FsExt::try_fs_scope(&webview);
+    upload_dir_zip_in_scope(url, dir_path, headers, on_progress, move |path| {
+        fs_scope
+            .as_ref()
+            .is_some_and(|scope| scope.is_allowed(path))
+    })
+    .await
+}
+
+/// Uploads `dir_path` as a zip archive, every entry of it must be allowed by `is_allowed`.
+async fn upload_dir_zip_in_scope<F: Fn(&Path) -> bool + Send + 'static>(
+    url: String,
+    dir_path: String,
+    headers: HashMap<String, String>,
+    on_progress: Channel<ProgressPayload>,
+    is_allowed: F,
+) -> Result<String> {
+    tokio::spawn(async move {
+        // Walk the directory up front so the archive length is known
+        let zip = tokio::task::spawn_blocking(move || ZipDir::new(dir_path.as_ref(), is_allowed))
+            .await
+            .map_err(|e| Error::Io(std::io::Error::other(e.to_string())))??;
+        let zip_len = zip.len();
+
+        // Create the request and stream the archive into the body
+        let client = reqwest::Client::new();
+        let mut request = client
+            .post(&url)
+            .header(reqwest::header::CONTENT_TYPE, "application/zip")
+            .header(reqwest::header::CONTENT_LENGTH, zip_len)
+            .body(stream_to_body(on_progress, zip.into_stream(), zip_len));
+
+        // Loop through the headers keys and values
+        // and add them to the request object.
+        for (key, value) in headers {
+            request = request.header(&key, value);
+        }
+
+        let response = request.send().await?;
+        if response.status().is_success() {
+            response.text().await.map_err(Into::into)
+        } else {
+            Err(Error::HttpErrorCode(
+                response.status().as_u16(),
+                response.text().await.unwrap_or_default(),
+            ))
+        }
+    })
+    .await
+    .map_err(|e| Error::Io(std::io::Error::other(e.to_string())))?
+}
+
 fn file_to_body(channel: Channel<ProgressPayload>, file: File, file_len: u64) -> reqwest::Body {
     let stream = FramedRead::new(file, BytesCodec::new()).map_ok(|r| r.freeze());
+    stream_to_body(channel, stream, file_len)
+}
 
+fn stream_to_body<S>(channel: Channel<ProgressPayload>, stream: S, total: u64) -> reqwest::Body
+where
+    S: futures_util::Stream<Item = std::io::Result<tokio_util::bytes::Bytes>>
+        + Send
+        + Sync
+        + 'static,
+{
     let mut stats = TransferStats::default();
     reqwest::Body::wrap_stream(ReadProgressStream::new(
         stream,
@@ -165,7 +254,7 @@ fn file_to_body(channel: Channel<ProgressPayload>, file: File, file_len: u64) ->
             let _ = channel.send(ProgressPayload {
                 progress,
                 progress_total: stats.total_transferred,
-                total: file_len,
+                total,
                 transfer_speed: stats.transfer_speed,
             });
         }),
@@ -174,7 +263,7 @@ fn file_to_body(channel: Channel<ProgressPayload>, file: File, file_len: u64) ->
 
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
     PluginBuilder::new("upload")
-        .invoke_handler(tauri::generate_handler![download, upload])
+        .invoke_handler(tauri::generate_handler![download, upload, upload_dir_zip])
         .build()
 }
 
@@ -209,6 +298,42 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn should_name_download_after_content_disposition() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/files/42")
+            .with_header(
+                "content-disposition",
+                r#"attachment; filename="../../report.pdf""#,
+            )
+            .with_body("report")
+            .create_async()
+            .await;
+        let _fallback = server
+            .mock("GET", "/files/notes.txt")
+            .with_body("notes")
+            .create_async()
+            .await;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let dir_path = dir.to_string_lossy().into_owned();
+
+        // the suggested name can't escape the directory
+        let path = download_to(server.url() + "/files/42", dir_path.clone(), true)
+            .await
+            .unwrap();
+        assert_eq!(PathBuf::from(&path), dir.join("report.pdf"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "report");
+
+        // without the header, the file is named after the URL
+        let path = download_to(server.url() + "/files/notes.txt", dir_path, true)
+            .await
+            .unwrap();
+        assert_eq!(PathBuf::from(&path), dir.join("notes.txt"));
+    }
+
     #[tokio::test]
     async fn should_error_on_upload_if_status_not_success() {
         let mocked_server = spawn_upload_server_mocked(500).await;
@@ -254,15 +379,117 @@ mod tests {
         assert_eq!(response_body, "upload successful");
     }
 
-    async fn download_file(url: String) -> Result<()> {
+    #[tokio::test]
+    async fn should_upload_dir_as_zip() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        std::fs::create_dir_all(dir.join("nested/empty")).unwrap();
+        std::fs::write(dir.join("a.txt"), "first file").unwrap();
+        std::fs::write(dir.join("nested/b.txt"), "second file").unwrap();
+
+        let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let received_ = received.clone();
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/upload_dir_test")
+            .match_header("content-type", "application/zip")
+            .match_request(move |request| {
+                *received_.lock().unwrap() = request.body().unwrap().clone();
+                true
+            })
+            .with_status(200)
+            .with_body("upload successful")
+            .create_async()
+            .await;
+
+        let sender: Channel<ProgressPayload> =
+            Channel::new(|msg: InvokeResponseBody| -> tauri::Result<()> {
+                let _ = msg;
+                Ok(())
+            });
+        let result = upload_dir_zip_in_scope(
+            server.url() + "/upload_dir_test",
+            dir.to_string_lossy().into_owned(),
+            HashMap::new(),
+            sender,
+            |_| true,
+        )
+        .await;
+        mock.assert();
+        assert_eq!(result.unwrap(), "upload successful");
+
+        let body = received.lock().unwrap().clone();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(body)).unwrap();
+        let mut names = archive.file_names().map(String::from).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["a.txt", "nested/", "nested/b.txt", "nested/empty/"]);
+
+        let mut contents = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("nested/b.txt").unwrap(), &mut contents)
+            .unwrap();
+        assert_eq!(contents, "second file");
+        assert!(archive.by_name("nested/empty/").unwrap().is_dir());
+    }
+
+    #[tokio::test]
+    async fn should_not_upload_dir_entries_outside_of_the_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("secrets")).unwrap();
+        std::fs::write(dir.path().join("a.txt"), "first file").unwrap();
+        std::fs::write(dir.path().join("secrets/key"), "secret").unwrap();
+
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/upload_dir_test")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let sender: Channel<ProgressPayload> =
+            Channel::new(|msg: InvokeResponseBody| -> tauri::Result<()> {
+                let _ = msg;
+                Ok(())
+            });
+        let result = upload_dir_zip_in_scope(
+            server.url() + "/upload_dir_test",
+            dir.path().to_string_lossy().into_owned(),
+            HashMap::new(),
+            sender,
+            |path| !path.ends_with("secrets/key"),
+        )
+        .await;
+        mock.assert();
+        assert!(
+            matches!(&result, Err(Error::PathNotAllowed(path)) if path.ends_with("secrets/key")),
+            "{result:?}"
+        );
+    }
+
+    async fn download_file(url: String) -> Result<String> {
         let file_path = concat!(env!("CARGO_MANIFEST_DIR"), "/test/test.txt").to_string();
+        download_to(url, file_path, false).await
+    }
+
+    async fn download_to(
+        url: String,
+        file_path: String,
+        use_content_disposition: bool,
+    ) -> Result<String> {
         let headers = HashMap::new();
         let sender: Channel<ProgressPayload> =
             Channel::new(|msg: InvokeResponseBody| -> tauri::Result<()> {
                 let _ = msg;
                 Ok(())
             });
-        download(url, file_path, headers, None, sender).await
+        download(
+            url,
+            file_path,
+            headers,
+            None,
+            sender,
+            Some(use_content_disposition),
+        )
+        .await
     }
 
     async fn upload_file(url: String) -> Result<String> {
@@ -0,0 +1,421 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Streams a directory as an uncompressed zip archive without writing it to disk.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll},
+    time::SystemTime,
+};
+
+use futures_util::Stream;
+use tokio::{fs::File, io::AsyncReadExt, sync::mpsc};
+use tokio_util::bytes::Bytes;
+
+use crate::{Error, Result};
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x04034b50;
+const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x08074b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIR_SIGNATURE: u32 = 0x06054b50;
+
+const LOCAL_HEADER_LEN: u64 = 30;
+const DATA_DESCRIPTOR_LEN: u64 = 16;
+const CENTRAL_HEADER_LEN: u64 = 46;
+const END_OF_CENTRAL_DIR_LEN: u64 = 22;
+
+/// Version 2.0, required for directory entries and data descriptors.
+const VERSION: u16 = 20;
+/// Sizes and CRC follow the data (bit 3) and names are UTF-8 (bit 11).
+const FLAGS: u16 = (1 << 3) | (1 << 11);
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug)]
+enum EntryKind {
+    Dir,
+    File { path: PathBuf, size: u64 },
+}
+
+#[derive(Debug)]
+struct Entry {
+    name: String,
+    kind: EntryKind,
+    dos_time: u16,
+    dos_date: u16,
+}
+
+impl Entry {
+    fn size(&self) -> u64 {
+        match self.kind {
+            EntryKind::Dir => 0,
+            EntryKind::File { size, .. } => size,
+        }
+    }
+
+    fn local_header(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(LOCAL_HEADER_LEN as usize + self.name.len());
+        put_u32(&mut buf, LOCAL_HEADER_SIGNATURE);
+        put_u16(&mut buf, VERSION);
+        put_u16(&mut buf, FLAGS);
+        put_u16(&mut buf, 0); // stored
+        put_u16(&mut buf, self.dos_time);
+        put_u16(&mut buf, self.dos_date);
+        // crc and sizes are written to the data descriptor
+        put_u32(&mut buf, 0);
+        put_u32(&mut buf, 0);
+        put_u32(&mut buf, 0);
+        put_u16(&mut buf, self.name.len() as u16);
+        put_u16(&mut buf, 0);
+        buf.extend_from_slice(self.name.as_bytes());
+        buf
+    }
+
+    fn data_descriptor(&self, crc: u32) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(DATA_DESCRIPTOR_LEN as usize);
+        put_u32(&mut buf, DATA_DESCRIPTOR_SIGNATURE);
+        put_u32(&mut buf, crc);
+        put_u32(&mut buf, self.size() as u32);
+        put_u32(&mut buf, self.size() as u32);
+        buf
+    }
+
+    fn central_header(&self, crc: u32, offset: u64) -> Vec<u8> {
+        let external_attributes: u32 = match self.kind {
+            // MS-DOS directory attribute and rwxr-xr-x
+            EntryKind::Dir => 0x10 | (0o40755 << 16),
+            EntryKind::File { .. } => 0o100644 << 16,
+        };
+
+        let mut buf = Vec::with_capacity(CENTRAL_HEADER_LEN as usize + self.name.len());
+        put_u32(&mut buf, CENTRAL_HEADER_SIGNATURE);
+        put_u16(&mut buf, (3 << 8) | VERSION); // made by unix
+        put_u16(&mut buf, VERSION);
+        put_u16(&mut buf, FLAGS);
+        put_u16(&mut buf, 0);
+        put_u16(&mut buf, self.dos_time);
+        put_u16(&mut buf, self.dos_date);
+        put_u32(&mut buf, crc);
+        put_u32(&mut buf, self.size() as u32);
+        put_u32(&mut buf, self.size() as u32);
+        put_u16(&mut buf, self.name.len() as u16);
+        put_u16(&mut buf, 0); // extra field length
+        put_u16(&mut buf, 0); // comment length
+        put_u16(&mut buf, 0); // disk number
+        put_u16(&mut buf, 0); // internal attributes
+        put_u32(&mut buf, external_attributes);
+        put_u32(&mut buf, offset as u32);
+        buf.extend_from_slice(self.name.as_bytes());
+        buf
+    }
+}
+
+/// A directory laid out as a zip archive, ready to be streamed.
+#[derive(Debug)]
+pub struct ZipDir {
+    entries: Vec<Entry>,
+    len: u64,
+}
+
+impl ZipDir {
+    /// Walks `root` and plans the archive.
+    ///
+    /// Every directory gets its own entry so empty directories survive the round trip.
+    /// Symbolic links are never followed into directories; a link to a file is archived
+    /// with the file contents only if its target lives inside `root`, otherwise it is skipped.
+    ///
+    /// Fails with [`Error::PathNotAllowed`] if `root` or any entry in it, or the target of a
+    /// symbolic link, is not allowed by `is_allowed`.
+    pub fn new(root: &Path, is_allowed: impl Fn(&Path) -> bool) -> Result<Self> {
+        let root = dunce::canonicalize(root)?;
+        if !root.is_dir() {
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{} is not a directory", root.display()),
+            )));
+        }
+        if !is_allowed(&root) {
+            return Err(Error::PathNotAllowed(root));
+        }
+
+        let mut entries = Vec::new();
+        walk(&root, &root, "", &is_allowed, &mut entries)?;
+        Self::plan(entries)
+    }
+
+    /// Lays out `entries`, failing if the archive would need zip64.
+    fn plan(entries: Vec<Entry>) -> Result<Self> {
+        if entries.len() >= u16::MAX as usize {
+            return Err(Error::Zip(format!(
+                "{} entries exceed the {} supported without zip64",
+                entries.len(),
+                u16::MAX
+            )));
+        }
+
+        let mut len = 0;
+        for entry in &entries {
+            len += LOCAL_HEADER_LEN
+                + entry.name.len() as u64
+                + entry.size()
+                + DATA_DESCRIPTOR_LEN
+                + CENTRAL_HEADER_LEN
+                + entry.name.len() as u64;
+        }
+        len += END_OF_CENTRAL_DIR_LEN;
+        if len > u32::MAX as u64 {
+            return Err(Error::Zip(
+                "archive exceeds the 4 GiB supported without zip64".into(),
+            ));
+        }
+
+        Ok(Self { entries, len })
+    }
+
+    /// The exact length in bytes of the archive.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Streams the archive, reading the files as the stream is polled.
+    pub fn into_stream(self) -> ZipStream {
+        let (tx, rx) = mpsc::channel(4);
+        tokio::spawn(async move {
+            if let Err(e) = self.write(&tx).await {
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+        ZipStream(rx)
+    }
+
+    async fn write(self, tx: &mpsc::Sender<std::io::Result<Bytes>>) -> std::io::Result<()> {
+        let send = |chunk: Vec<u8>| async move {
+            tx.send(Ok(chunk.into()))
+                .await
+                .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "body dropped"))
+        };
+
+        let mut offset = 0;
+        let mut central_directory = Vec::new();
+        for entry in &self.entries {
+            let local_header = entry.local_header();
+            let header_len = local_header.len() as u64;
+            send(local_header).await?;
+
+            let mut hasher = crc32fast::Hasher::new();
+            if let EntryKind::File { path, size } = &entry.kind {
+                let mut file = File::open(path).await?;
+                let mut written = 0;
+                loop {
+                    let mut chunk = vec![0; CHUNK_SIZE];
+                    let n = file.read(&mut chunk).await?;
+                    if n == 0 {
+                        break;
+                    }
+                    chunk.truncate(n);
+                    written += n as u64;
+                    if written > *size {
+                        break;
+                    }
+                    hasher.update(&chunk);
+                    send(chunk).await?;
+                }
+                if written != *size {
+                    return Err(std::io::Error::other(format!(
+                        "{} changed size while being uploaded",
+                        path.display()
+                    )));
+                }
+            }
+
+            let crc = hasher.finalize();
+            send(entry.data_descriptor(crc)).await?;
+            central_directory.extend(entry.central_header(crc, offset));
+            offset += header_len + entry.size() + DATA_DESCRIPTOR_LEN;
+        }
+
+        let mut end = Vec::with_capacity(END_OF_CENTRAL_DIR_LEN as usize);
+        put_u32(&mut end, END_OF_CENTRAL_DIR_SIGNATURE);
+        put_u16(&mut end, 0);
+        put_u16(&mut end, 0);
+        put_u16(&mut end, self.entries.len() as u16);
+        put_u16(&mut end, self.entries.len() as u16);
+        put_u32(&mut end, central_directory.len() as u32);
+        put_u32(&mut end, offset as u32);
+        put_u16(&mut end, 0);
+        central_directory.extend(end);
+        send(central_directory).await
+    }
+}
+
+/// The archive bytes, produced by a background task reading the files.
+pub struct ZipStream(mpsc::Receiver<std::io::Result<Bytes>>);
+
+impl Stream for ZipStream {
+    type Item = std::io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_recv(cx)
+    }
+}
+
+fn walk(
+    root: &Path,
+    dir: &Path,
+    prefix: &str,
+    is_allowed: &impl Fn(&Path) -> bool,
+    entries: &mut Vec<Entry>,
+) -> Result<()> {
+    let mut children = fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+    children.sort_by_key(|c| c.file_name());
+
+    for child in children {
+        let path = child.path();
+        if !is_allowed(&path) {
+            return Err(Error::PathNotAllowed(path));
+        }
+        let name = format!("{prefix}{}", child.file_name().to_string_lossy());
+        let metadata = fs::symlink_metadata(&path)?;
+        let (dos_time, dos_date) = dos_date_time(metadata.modified().ok());
+
+        if metadata.is_dir() {
+            let dir_name = format!("{name}/");
+            entries.push(Entry {
+                name: dir_name.clone(),
+                kind: EntryKind::Dir,
+                dos_time,
+                dos_date,
+            });
+            walk(root, &path, &dir_name, is_allowed, entries)?;
+        } else if metadata.is_file() {
+            check_size(&path, metadata.len())?;
+            entries.push(Entry {
+                name,
+                kind: EntryKind::File {
+                    path,
+                    size: metadata.len(),
+                },
+                dos_time,
+                dos_date,
+            });
+        } else if metadata.is_symlink() {
+            match dunce::canonicalize(&path) {
+                Ok(target) if target.starts_with(root) && target.is_file() => {
+                    if !is_allowed(&target) {
+                        return Err(Error::PathNotAllowed(target));
+                    }
+                    let size = fs::metadata(&target)?.len();
+                    check_size(&path, size)?;
+                    entries.push(Entry {
+                        name,
+                        kind: EntryKind::File { path: target, size },
+                        dos_time,
+                        dos_date,
+                    });
+                }
+                _ => log::warn!("skipping symbolic link {}", path.display()),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn check_size(path: &Path, size: u64) -> Result<()> {
+    if size > u32::MAX as u64 {
+        Err(Error::Zip(format!(
+            "{} exceeds the 4 GiB supported without zip64",
+            path.display()
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Converts a timestamp to the MS-DOS `(time, date)` pair, in UTC.
+fn dos_date_time(time: Option<SystemTime>) -> (u16, u16) {
+    let secs = time
+        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+    let (hour, minute, second) = (rem / 3600, rem % 3600 / 60, rem % 60);
+
+    // civil date from days since the unix epoch
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    // MS-DOS timestamps start in 1980
+    if !(1980..=2107).contains(&year) {
+        return (0, (1 << 5) | 1);
+    }
+
+    let time = ((hour << 11) | (minute << 5) | (second / 2)) as u16;
+    let date = (((year - 1980) << 9) | (month << 5) | day) as u16;
+    (time, date)
+}
+
+fn put_u16(buf: &mut Vec<u8>, value: u16) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dos_date_time_conversion() {
+        // 2024-02-29T13:45:30Z
+        let time = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1709214330);
+        let (time, date) = dos_date_time(Some(time));
+        assert_eq!(date, (44 << 9) | (2 << 5) | 29);
+        assert_eq!(time, (13 << 11) | (45 << 5) | 15);
+    }
+
+    #[test]
+    fn rejects_archives_that_need_zip64() {
+        let dir = |n: usize| Entry {
+            name: format!("{n}/"),
+            kind: EntryKind::Dir,
+            dos_time: 0,
+            dos_date: 0,
+        };
+        assert!(ZipDir::plan((0..u16::MAX as usize - 1).map(dir).collect()).is_ok());
+        assert!(matches!(
+            ZipDir::plan((0..u16::MAX as usize).map(dir).collect()),
+            Err(Error::Zip(_))
+        ));
+
+        let file = |size: u64| Entry {
+            name: "large.bin".into(),
+            kind: EntryKind::File {
+                path: PathBuf::new(),
+                size,
+            },
+            dos_time: 0,
+            dos_date: 0,
+        };
+        assert!(ZipDir::plan(vec![file(u32::MAX as u64 / 2)]).is_ok());
+        assert!(matches!(
+            ZipDir::plan(vec![file(u32::MAX as u64 / 2), file(u32::MAX as u64 / 2)]),
+            Err(Error::Zip(_))
+        ));
+    }
+}
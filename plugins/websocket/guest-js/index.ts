@@ -37,6 +37,12 @@ export interface ConnectionConfig {
    * Additional connect request headers.
    */
   headers?: HeadersInit
+  /**
+   * Local IP address to bind the connection to, e.g. to send the traffic through a specific network interface.
+   *
+   * @since 2.5.0
+   */
+  localAddress?: string
 }
 
 export interface MessageKind<T, D> {
@@ -49,6 +55,24 @@ export interface CloseFrame {
   reason: string
 }
 
+/**
+ * Traffic of a connection since it was opened, see {@linkcode WebSocket.stats}.
+ *
+ * @since 2.5.0
+ */
+export interface ConnectionStats {
+  /** Payload bytes of the messages sent. */
+  sentBytes: number
+  /** Payload bytes of the messages received. */
+  recvBytes: number
+  /** Number of messages sent. */
+  sentMsgs: number
+  /** Number of messages received, including pings, pongs and the close frame. */
+  recvMsgs: number
+  /** When the connection was opened, in milliseconds since the Unix epoch. */
+  openedAt: number
+}
+
 export type Message =
   | MessageKind<'Text', string>
   | MessageKind<'Binary', number[]>
@@ -116,6 +140,17 @@ export default class WebSocket {
     })
   }
 
+  /**
+   * Get the traffic of this connection since it was opened.
+   *
+   * @since 2.5.0
+   */
+  async stats(): Promise<ConnectionStats> {
+    return await invoke<ConnectionStats>('plugin:websocket|connection_stats', {
+      id: this.id
+    })
+  }
+
   async disconnect(): Promise<void> {
     await this.send({
       type: 'Close',
@@ -17,14 +17,19 @@ use tauri::{
     plugin::{Builder as PluginBuilder, TauriPlugin},
     Manager, Runtime, State, Window,
 };
-use tokio::{net::TcpStream, sync::Mutex};
+use tokio::{
+    net::{TcpSocket, TcpStream},
+    sync::Mutex,
+};
 #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
-use tokio_tungstenite::connect_async_tls_with_config;
+use tokio_tungstenite::{client_async_tls_with_config, connect_async_tls_with_config};
 #[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
-use tokio_tungstenite::connect_async_with_config;
+use tokio_tungstenite::{client_async_with_config, connect_async_with_config};
 use tokio_tungstenite::{
     tungstenite::{
         client::IntoClientRequest,
+        error::UrlError,
+        handshake::client::Request,
         protocol::{CloseFrame as ProtocolCloseFrame, WebSocketConfig},
         Message,
     },
@@ -32,7 +37,13 @@ use tokio_tungstenite::{
 };
 
 use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 use std::str::FromStr;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 type Id = u32;
 type WebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
@@ -49,6 +60,8 @@ enum Error {
     InvalidHeaderValue(#[from] tokio_tungstenite::tungstenite::http::header::InvalidHeaderValue),
     #[error(transparent)]
     InvalidHeaderName(#[from] tokio_tungstenite::tungstenite::http::header::InvalidHeaderName),
+    #[error("failed to bind to local address {0}: {1}")]
+    Bind(IpAddr, std::io::Error),
 }
 
 impl Serialize for Error {
@@ -61,7 +74,74 @@ impl Serialize for Error {
 }
 
 #[derive(Default)]
-struct ConnectionManager(Mutex<HashMap<Id, WebSocketWriter>>);
+struct ConnectionManager(Mutex<HashMap<Id, Connection>>);
+
+struct Connection {
+    writer: WebSocketWriter,
+    counters: Arc<Counters>,
+}
+
+impl Connection {
+    async fn send(&mut self, message: Message) -> Result<()> {
+        let len = message.len() as u64;
+        self.writer.send(message).await?;
+        self.counters.sent_bytes.fetch_add(len, Ordering::Relaxed);
+        self.counters.sent_msgs.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// Traffic of a connection since it was opened, counting the payload of every message.
+struct Counters {
+    sent_bytes: AtomicU64,
+    recv_bytes: AtomicU64,
+    sent_msgs: AtomicU64,
+    recv_msgs: AtomicU64,
+    opened_at: SystemTime,
+}
+
+impl Counters {
+    fn new() -> Self {
+        Self {
+            sent_bytes: Default::default(),
+            recv_bytes: Default::default(),
+            sent_msgs: Default::default(),
+            recv_msgs: Default::default(),
+            opened_at: SystemTime::now(),
+        }
+    }
+
+    fn received(&self, message: &Message) {
+        self.recv_bytes
+            .fetch_add(message.len() as u64, Ordering::Relaxed);
+        self.recv_msgs.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn stats(&self) -> ConnectionStats {
+        ConnectionStats {
+            sent_bytes: self.sent_bytes.load(Ordering::Relaxed),
+            recv_bytes: self.recv_bytes.load(Ordering::Relaxed),
+            sent_msgs: self.sent_msgs.load(Ordering::Relaxed),
+            recv_msgs: self.recv_msgs.load(Ordering::Relaxed),
+            opened_at: self
+                .opened_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct ConnectionStats {
+    sent_bytes: u64,
+    recv_bytes: u64,
+    sent_msgs: u64,
+    recv_msgs: u64,
+    /// Milliseconds since the Unix epoch.
+    opened_at: u64,
+}
 
 #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
 struct TlsConnector(Mutex<Option<Connector>>);
@@ -84,6 +164,7 @@ pub(crate) struct ConnectionConfig {
     #[serde(default)]
     pub accept_unmasked_frames: bool,
     pub headers: Option<Vec<(String, String)>>,
+    pub local_address: Option<IpAddr>,
 }
 
 impl From<ConnectionConfig> for WebSocketConfig {
@@ -163,21 +244,57 @@ async fn connect<R: Runtime>(
         None => None,
     };
 
-    #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
-    let (ws_stream, _) =
-        connect_async_tls_with_config(request, config.map(Into::into), false, tls_connector)
-            .await?;
-    #[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
-    let (ws_stream, _) = connect_async_with_config(request, config.map(Into::into), false).await?;
+    let local_address = config.as_ref().and_then(|c| c.local_address);
+    let config = config.map(Into::into);
+
+    let ws_stream = match local_address {
+        Some(local_address) => {
+            let stream = connect_from(local_address, &request).await?;
+            #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+            let (ws_stream, _) =
+                client_async_tls_with_config(request, stream, config, tls_connector).await?;
+            #[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
+            let (ws_stream, _) = {
+                if request.uri().scheme_str() == Some("wss") {
+                    return Err(tokio_tungstenite::tungstenite::Error::Url(
+                        UrlError::TlsFeatureNotEnabled,
+                    )
+                    .into());
+                }
+                client_async_with_config(request, MaybeTlsStream::Plain(stream), config).await?
+            };
+            ws_stream
+        }
+        None => {
+            #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+            let (ws_stream, _) =
+                connect_async_tls_with_config(request, config, false, tls_connector).await?;
+            #[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
+            let (ws_stream, _) = connect_async_with_config(request, config, false).await?;
+            ws_stream
+        }
+    };
 
     tauri::async_runtime::spawn(async move {
-        let (write, read) = ws_stream.split();
+        let (writer, read) = ws_stream.split();
+        let counters = Arc::new(Counters::new());
         let manager = window.state::<ConnectionManager>();
-        manager.0.lock().await.insert(id, write);
+        manager.0.lock().await.insert(
+            id,
+            Connection {
+                writer,
+                counters: counters.clone(),
+            },
+        );
         read.for_each(move |message| {
             let window_ = window.clone();
             let on_message_ = on_message.clone();
+            let counters = counters.clone();
             async move {
+                if let Ok(message) = &message {
+                    counters.received(message);
+                }
+
                 if let Ok(Message::Close(_)) = message {
                     let manager = window_.state::<ConnectionManager>();
                     manager.0.lock().await.remove(&id);
@@ -216,14 +333,69 @@ async fn connect<R: Runtime>(
     Ok(id)
 }
 
+/// Opens the TCP connection for `request` with its local end bound to `local_address`.
+async fn connect_from(local_address: IpAddr, request: &Request) -> Result<TcpStream> {
+    let uri = request.uri();
+    let host = uri
+        .host()
+        .map(|host| host.trim_start_matches('[').trim_end_matches(']'))
+        .ok_or(tokio_tungstenite::tungstenite::Error::Url(
+            UrlError::NoHostName,
+        ))?;
+    let port = uri
+        .port_u16()
+        .or_else(|| match uri.scheme_str() {
+            Some("wss") => Some(443),
+            Some("ws") => Some(80),
+            _ => None,
+        })
+        .ok_or(tokio_tungstenite::tungstenite::Error::Url(
+            UrlError::UnsupportedUrlScheme,
+        ))?;
+
+    let addrs = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(tokio_tungstenite::tungstenite::Error::Io)?;
+
+    let mut last_error = None;
+    // only addresses of the same family can be reached from `local_address`
+    for addr in addrs.filter(|addr| addr.is_ipv4() == local_address.is_ipv4()) {
+        let socket = if addr.is_ipv4() {
+            TcpSocket::new_v4()
+        } else {
+            TcpSocket::new_v6()
+        }
+        .map_err(|e| Error::Bind(local_address, e))?;
+        socket
+            .bind(SocketAddr::new(local_address, 0))
+            .map_err(|e| Error::Bind(local_address, e))?;
+
+        match socket.connect(addr).await {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    Err(match last_error {
+        Some(e) => tokio_tungstenite::tungstenite::Error::Io(e).into(),
+        None => Error::Bind(
+            local_address,
+            std::io::Error::new(
+                std::io::ErrorKind::AddrNotAvailable,
+                format!("{host} has no address of the same family"),
+            ),
+        ),
+    })
+}
+
 #[tauri::command]
 async fn send(
     manager: State<'_, ConnectionManager>,
     id: Id,
     message: WebSocketMessage,
 ) -> Result<()> {
-    if let Some(write) = manager.0.lock().await.get_mut(&id) {
-        write
+    if let Some(connection) = manager.0.lock().await.get_mut(&id) {
+        connection
             .send(match message {
                 WebSocketMessage::Text(t) => Message::Text(t.into()),
                 WebSocketMessage::Binary(t) => Message::Binary(t.into()),
@@ -234,13 +406,26 @@ async fn send(
                     reason: v.reason.into(),
                 })),
             })
-            .await?;
-        Ok(())
+            .await
     } else {
         Err(Error::ConnectionNotFound(id))
     }
 }
 
+#[tauri::command]
+async fn connection_stats(
+    manager: State<'_, ConnectionManager>,
+    id: Id,
+) -> Result<ConnectionStats> {
+    manager
+        .0
+        .lock()
+        .await
+        .get(&id)
+        .map(|connection| connection.counters.stats())
+        .ok_or(Error::ConnectionNotFound(id))
+}
+
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
     Builder::default().build()
 }
@@ -264,7 +449,7 @@ impl Builder {
 
     pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
         PluginBuilder::new("websocket")
-            .invoke_handler(tauri::generate_handler![connect, send])
+            .invoke_handler(tauri::generate_handler![connect, send, connection_stats])
             .setup(|app, _api| {
                 app.manage(ConnectionManager::default());
                 #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
@@ -274,3 +459,84 @@ impl Builder {
             .build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn connects_from_local_address() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (stream, peer) = listener.accept().await.unwrap();
+            tokio_tungstenite::accept_async(stream).await.unwrap();
+            peer
+        });
+
+        let local_address = IpAddr::from([127, 0, 0, 1]);
+        let request = url.into_client_request().unwrap();
+        let stream = connect_from(local_address, &request).await.unwrap();
+        tokio_tungstenite::client_async(request, stream)
+            .await
+            .unwrap();
+
+        assert_eq!(server.await.unwrap().ip(), local_address);
+    }
+
+    #[tokio::test]
+    async fn counts_traffic() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            // echo the first message twice
+            let message = ws.next().await.unwrap().unwrap();
+            ws.send(message.clone()).await.unwrap();
+            ws.send(message).await.unwrap();
+        });
+
+        let (ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+        let (writer, mut read) = ws.split();
+        let mut connection = Connection {
+            writer,
+            counters: Arc::new(Counters::new()),
+        };
+        let opened_at = connection.counters.stats().opened_at;
+        assert!(opened_at > 0);
+
+        connection
+            .send(Message::Text("hello".into()))
+            .await
+            .unwrap();
+        for _ in 0..2 {
+            let message = read.next().await.unwrap().unwrap();
+            connection.counters.received(&message);
+        }
+
+        assert_eq!(
+            connection.counters.stats(),
+            ConnectionStats {
+                sent_bytes: 5,
+                recv_bytes: 10,
+                sent_msgs: 1,
+                recv_msgs: 2,
+                opened_at,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_to_bind_unassigned_address() {
+        // TEST-NET-1, reserved for documentation and never assigned to an interface
+        let local_address = IpAddr::from([192, 0, 2, 1]);
+        let request = "ws://127.0.0.1:9".into_client_request().unwrap();
+
+        assert!(matches!(
+            connect_from(local_address, &request).await,
+            Err(Error::Bind(address, _)) if address == local_address
+        ));
+    }
+}
@@ -21,15 +21,21 @@ use tauri::{
 use std::{
     collections::{HashMap, HashSet},
     fs::create_dir_all,
-    io::BufReader,
-    path::PathBuf,
-    sync::{Arc, Mutex},
+    io::{BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
 };
 
 mod cmd;
 
 type LabelMapperFn = dyn Fn(&str) -> &str + Send + Sync;
 type FilterCallbackFn = dyn Fn(&str) -> bool + Send + Sync;
+type RestoreValidatorFn = dyn Fn(&WindowState) -> bool + Send + Sync;
 
 /// Default filename used to store window state.
 ///
@@ -44,6 +50,8 @@ pub enum Error {
     Tauri(#[from] tauri::Error),
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
+    #[error("invalid window state profile name: {0}")]
+    InvalidProfile(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -71,11 +79,42 @@ struct PluginState {
     pub(crate) state_flags: StateFlags,
     filename: String,
     dir: Option<PathBuf>,
+    /// The profile whose state file is in use, see [`AppHandleExt::set_profile`].
+    profile: Mutex<Option<String>>,
     map_label: Option<Box<LabelMapperFn>>,
+    restore_validator: Option<Box<RestoreValidatorFn>>,
+    tracking: Arc<WindowTracking>,
+    /// Notifies the autosave thread of window changes, see [`Builder::save_policy`].
+    autosave: Option<Sender<Instant>>,
 }
 
+impl PluginState {
+    /// The name of the state file of the active profile.
+    fn filename(&self) -> String {
+        match &*self.profile.lock().unwrap() {
+            Some(profile) => profile_filename(&self.filename, profile),
+            None => self.filename.clone(),
+        }
+    }
+
+    fn dir<R: Runtime>(&self, app: &AppHandle<R>) -> PathBuf {
+        self.dir
+            .clone()
+            .unwrap_or_else(|| app.path().app_config_dir().unwrap_or_default())
+    }
+}
+
+/// Inserts the profile name before the extension of `filename`, e.g. `.window-state.{profile}.json`.
+fn profile_filename(filename: &str, profile: &str) -> String {
+    match filename.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => format!("{stem}.{profile}.{extension}"),
+        _ => format!("{filename}.{profile}"),
+    }
+}
+
+/// The saved state of a window.
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
-struct WindowState {
+pub struct WindowState {
     width: u32,
     height: u32,
     x: i32,
@@ -89,6 +128,10 @@ struct WindowState {
     visible: bool,
     decorated: bool,
     fullscreen: bool,
+    // the scale factor the physical size was saved at,
+    // missing from files written by older versions
+    #[serde(default)]
+    scale_factor: Option<f64>,
 }
 
 impl Default for WindowState {
@@ -104,6 +147,66 @@ impl Default for WindowState {
             visible: true,
             decorated: true,
             fullscreen: Default::default(),
+            scale_factor: Default::default(),
+        }
+    }
+}
+
+impl WindowState {
+    /// The saved inner width of the window, in physical pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The saved inner height of the window, in physical pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The saved outer x position of the window, in physical pixels.
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    /// The saved outer y position of the window, in physical pixels.
+    pub fn y(&self) -> i32 {
+        self.y
+    }
+
+    /// Whether the window was maximized.
+    pub fn maximized(&self) -> bool {
+        self.maximized
+    }
+
+    /// Whether the window was visible.
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Whether the window was decorated.
+    pub fn decorated(&self) -> bool {
+        self.decorated
+    }
+
+    /// Whether the window was fullscreen.
+    pub fn fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    /// The scale factor of the window when its state was saved, if known.
+    pub fn scale_factor(&self) -> Option<f64> {
+        self.scale_factor
+    }
+
+    /// The saved inner size, converted to physical pixels at the given scale factor
+    /// so the window keeps the same logical size it was saved with.
+    fn size_at(&self, scale_factor: f64) -> PhysicalSize<u32> {
+        let size = PhysicalSize::new(self.width, self.height);
+        match self.scale_factor {
+            Some(saved) if saved != scale_factor => {
+                size.to_logical::<f64>(saved).to_physical(scale_factor)
+            }
+            _ => size,
         }
     }
 }
@@ -111,51 +214,135 @@ impl Default for WindowState {
 struct WindowStateCache(Arc<Mutex<HashMap<String, WindowState>>>);
 /// Used to prevent deadlocks from resize and position event listeners setting the cached state on restoring states
 struct RestoringWindowState(Mutex<()>);
+/// Saved sizes of windows restored as maximized, applied once the window is unmaximized
+struct DeferredWindowSizes(Mutex<HashMap<String, PhysicalSize<u32>>>);
+
+/// When the window states are saved to disk while the windows change, see [`Builder::save_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SavePolicy {
+    /// How long the windows must stay unchanged before their states are saved.
+    pub debounce: Duration,
+    /// The longest a change waits to be saved while the windows keep changing.
+    pub max_interval: Duration,
+}
+
+/// Schedules the saves of window changes under a [`SavePolicy`].
+struct Autosave {
+    policy: SavePolicy,
+    /// Times of the first and the last change not saved yet.
+    unsaved: Option<(Instant, Instant)>,
+}
+
+impl Autosave {
+    fn new(policy: SavePolicy) -> Self {
+        Self {
+            policy,
+            unsaved: None,
+        }
+    }
+
+    fn changed(&mut self, at: Instant) {
+        let first = self.unsaved.map_or(at, |(first, _)| first);
+        self.unsaved = Some((first, at));
+    }
+
+    /// When the unsaved changes are due to be saved.
+    fn deadline(&self) -> Option<Instant> {
+        self.unsaved.map(|(first, last)| {
+            (last + self.policy.debounce).min(first + self.policy.max_interval)
+        })
+    }
+
+    fn saved(&mut self) {
+        self.unsaved = None;
+    }
+
+    /// Saves the window states from a background thread as the changes sent to the returned channel are due.
+    fn spawn<R: Runtime>(mut self, app: AppHandle<R>, flags: StateFlags) -> Sender<Instant> {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || loop {
+            let received = match self.deadline() {
+                Some(deadline) => {
+                    rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match received {
+                Ok(at) => self.changed(at),
+                Err(RecvTimeoutError::Timeout) => {
+                    self.saved();
+                    if let Err(e) = app.save_window_state(flags) {
+                        log::error!("failed to save the window state: {e}");
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        });
+        tx
+    }
+}
+
+/// Updates `state` from the open windows and writes it to the file of the active profile.
+///
+/// The caller holds the [`WindowStateCache`] lock, which [`AppHandleExt::set_profile`] also holds
+/// while switching profiles.
+fn save_window_states<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &mut HashMap<String, WindowState>,
+    flags: StateFlags,
+) -> Result<()> {
+    let plugin_state = app.state::<PluginState>();
+    let app_dir = plugin_state.dir(app);
+    let state_path = app_dir.join(plugin_state.filename());
+    let windows = app.webview_windows();
+
+    for (label, s) in state.iter_mut() {
+        let window = if let Some(map) = &plugin_state.map_label {
+            windows
+                .iter()
+                .find_map(|(l, window)| (map(l) == label).then_some(window))
+        } else {
+            windows.get(label)
+        };
+
+        if let Some(window) = window {
+            window.update_state(s, flags)?;
+        }
+    }
+
+    create_dir_all(app_dir)?;
+    write_atomic(&state_path, &serde_json::to_vec_pretty(&*state)?)?;
+
+    Ok(())
+}
 
 pub trait AppHandleExt {
     /// Saves all open windows state to disk
     fn save_window_state(&self, flags: StateFlags) -> Result<()>;
-    /// Get the name of the file used to store window state.
+    /// Get the name of the file used to store window state, the file of the active profile if one is set.
     fn filename(&self) -> String;
     /// Get the directory used to store window state.
     fn directory(&self) -> Option<String>;
+    /// Switches to the window states of `profile`, saved next to the default file,
+    /// e.g. in `.window-state.{profile}.json`, or back to the default file with `None`.
+    ///
+    /// The states of the open windows are saved to the current file first.
+    /// With `restore`, the open windows are then restored to their state in the new profile,
+    /// otherwise they keep their layout until the app is restarted or the profile is switched again.
+    fn set_profile(&self, profile: Option<&str>, restore: bool) -> Result<()>;
+    /// Get the name of the active profile, `None` when using the default file.
+    fn profile(&self) -> Option<String>;
 }
 
 impl<R: Runtime> AppHandleExt for tauri::AppHandle<R> {
     fn save_window_state(&self, flags: StateFlags) -> Result<()> {
-        let plugin_state = self.state::<PluginState>();
-        let app_dir = plugin_state
-            .dir
-            .as_ref()
-            .map(|dir| dir.clone())
-            .unwrap_or_else(|| self.path().app_config_dir().unwrap_or_default());
-        let state_path = app_dir.join(&plugin_state.filename);
-        let windows = self.webview_windows();
         let cache = self.state::<WindowStateCache>();
         let mut state = cache.0.lock().unwrap();
-
-        for (label, s) in state.iter_mut() {
-            let window = if let Some(map) = &plugin_state.map_label {
-                windows
-                    .iter()
-                    .find_map(|(l, window)| (map(l) == label).then_some(window))
-            } else {
-                windows.get(label)
-            };
-
-            if let Some(window) = window {
-                window.update_state(s, flags)?;
-            }
-        }
-
-        create_dir_all(app_dir)?;
-        std::fs::write(state_path, serde_json::to_vec_pretty(&*state)?)?;
-
-        Ok(())
+        save_window_states(self, &mut state, flags)
     }
 
     fn filename(&self) -> String {
-        self.state::<PluginState>().filename.clone()
+        self.state::<PluginState>().filename()
     }
 
     fn directory(&self) -> Option<String> {
@@ -164,10 +351,70 @@ impl<R: Runtime> AppHandleExt for tauri::AppHandle<R> {
             .as_ref()
             .map(|dir| dir.to_string_lossy().to_string())
     }
+
+    fn set_profile(&self, profile: Option<&str>, restore: bool) -> Result<()> {
+        if let Some(profile) = profile {
+            if profile.is_empty()
+                || profile.contains(['/', '\\', ':', '<', '>', '"', '|', '?', '*'])
+                || profile.chars().any(char::is_control)
+            {
+                return Err(Error::InvalidProfile(profile.into()));
+            }
+        }
+
+        let plugin_state = self.state::<PluginState>();
+        {
+            // the profile is switched under the cache lock, so a concurrent save either writes
+            // the old states to the old file or the new states to the new file
+            let cache = self.state::<WindowStateCache>();
+            let mut state = cache.0.lock().unwrap();
+            save_window_states(self, &mut state, plugin_state.state_flags)?;
+
+            *plugin_state.profile.lock().unwrap() = profile.map(Into::into);
+            *state =
+                load_saved_window_states(&plugin_state.dir(self).join(plugin_state.filename()))
+                    .unwrap_or_default();
+        }
+
+        for window in self.webview_windows().values() {
+            let label = plugin_state
+                .map_label
+                .as_ref()
+                .map(|map| map(window.label()))
+                .unwrap_or_else(|| window.label());
+            if plugin_state.tracking.excludes(window.label(), label) {
+                continue;
+            }
+
+            if restore {
+                window.restore_state(plugin_state.state_flags)?;
+            } else {
+                // keep saving the windows missing from the new profile
+                self.state::<WindowStateCache>()
+                    .0
+                    .lock()
+                    .unwrap()
+                    .entry(label.into())
+                    .or_default();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn profile(&self) -> Option<String> {
+        self.state::<PluginState>().profile.lock().unwrap().clone()
+    }
 }
 
 pub trait WindowExt {
     /// Restores this window state from disk
+    ///
+    /// The plugin already restores the state of every tracked window once it is ready, whenever it is created,
+    /// except for the windows passed to [`Builder::skip_initial_state`]. This can be called manually at any
+    /// time after a window is created, e.g. to restore a skipped window once its content is loaded.
+    ///
+    /// A window without a saved state gets an entry with its current state instead, saved with the other windows.
     fn restore_state(&self, flags: StateFlags) -> tauri::Result<()>;
 }
 
@@ -193,50 +440,41 @@ impl<R: Runtime> WindowExt for Window<R> {
 
         let mut should_show = true;
 
-        if let Some(state) = c
-            .get(label)
-            .filter(|state| state != &&WindowState::default())
-        {
-            if flags.contains(StateFlags::DECORATIONS) {
-                self.set_decorations(state.decorated)?;
-            }
-
-            if flags.contains(StateFlags::POSITION) {
+        if let Some(state) = saved_state(&c, label, plugin_state.restore_validator.as_deref()) {
+            let saved_monitor = if flags.contains(StateFlags::POSITION) {
                 let position = (state.x, state.y).into();
                 let size = (state.width, state.height).into();
-                // restore position to saved value if saved monitor exists
-                // otherwise, let the OS decide where to place the window
-                for m in self.available_monitors()? {
-                    if m.intersects(position, size) {
-                        self.set_position(PhysicalPosition {
-                            x: if state.maximized {
-                                state.prev_x
-                            } else {
-                                state.x
-                            },
-                            y: if state.maximized {
-                                state.prev_y
-                            } else {
-                                state.y
-                            },
-                        })?;
-                    }
-                }
-            }
+                self.available_monitors()?
+                    .into_iter()
+                    .find(|m| m.intersects(position, size))
+            } else {
+                None
+            };
 
-            if flags.contains(StateFlags::SIZE) {
-                self.set_size(PhysicalSize {
-                    width: state.width,
-                    height: state.height,
-                })?;
-            }
+            // the window is restored on the saved monitor, or stays where the OS placed it
+            let scale_factor = match &saved_monitor {
+                Some(monitor) => monitor.scale_factor(),
+                None => self.scale_factor()?,
+            };
 
-            if flags.contains(StateFlags::MAXIMIZED) && state.maximized {
-                self.maximize()?;
+            let (steps, deferred_size) =
+                restore_steps(state, flags, saved_monitor.is_some(), scale_factor);
+            for step in steps {
+                match step {
+                    RestoreStep::Decorations(decorated) => self.set_decorations(decorated)?,
+                    RestoreStep::Position(position) => self.set_position(position)?,
+                    RestoreStep::Size(size) => self.set_size(size)?,
+                    RestoreStep::Maximize => self.maximize()?,
+                    RestoreStep::Fullscreen(fullscreen) => self.set_fullscreen(fullscreen)?,
+                }
             }
 
-            if flags.contains(StateFlags::FULLSCREEN) {
-                self.set_fullscreen(state.fullscreen)?;
+            let deferred_sizes = self.state::<DeferredWindowSizes>();
+            let mut deferred_sizes = deferred_sizes.0.lock().unwrap();
+            if let Some(size) = deferred_size {
+                deferred_sizes.insert(self.label().into(), size);
+            } else {
+                deferred_sizes.remove(self.label());
             }
 
             should_show = state.visible;
@@ -247,6 +485,7 @@ impl<R: Runtime> WindowExt for Window<R> {
                 let size = self.inner_size()?;
                 metadata.width = size.width;
                 metadata.height = size.height;
+                metadata.scale_factor = Some(self.scale_factor()?);
             }
 
             if flags.contains(StateFlags::POSITION) {
@@ -283,6 +522,80 @@ impl<R: Runtime> WindowExt for Window<R> {
     }
 }
 
+/// A window operation applied when restoring a saved state.
+#[derive(Debug, PartialEq)]
+enum RestoreStep {
+    Decorations(bool),
+    Position(PhysicalPosition<i32>),
+    Size(PhysicalSize<u32>),
+    Maximize,
+    Fullscreen(bool),
+}
+
+/// Computes the operations restoring the given state, in order.
+///
+/// The saved size is converted to `scale_factor`, the scale factor of the monitor the
+/// window is restored on. Positions are kept in physical pixels since they locate the monitor.
+///
+/// A window saved as maximized is maximized without setting its saved size first,
+/// which would otherwise flash the window at that size. The saved size is returned
+/// separately so it can be applied once the window is unmaximized.
+fn restore_steps(
+    state: &WindowState,
+    flags: StateFlags,
+    on_saved_monitor: bool,
+    scale_factor: f64,
+) -> (Vec<RestoreStep>, Option<PhysicalSize<u32>>) {
+    let mut steps = Vec::new();
+    let mut deferred_size = None;
+    let maximize = flags.contains(StateFlags::MAXIMIZED) && state.maximized;
+
+    if flags.contains(StateFlags::DECORATIONS) {
+        steps.push(RestoreStep::Decorations(state.decorated));
+    }
+
+    // restore position to saved value if saved monitor exists
+    // otherwise, let the OS decide where to place the window
+    if flags.contains(StateFlags::POSITION) && on_saved_monitor {
+        steps.push(RestoreStep::Position(if state.maximized {
+            PhysicalPosition::new(state.prev_x, state.prev_y)
+        } else {
+            PhysicalPosition::new(state.x, state.y)
+        }));
+    }
+
+    if flags.contains(StateFlags::SIZE) {
+        let size = state.size_at(scale_factor);
+        if maximize {
+            deferred_size.replace(size);
+        } else {
+            steps.push(RestoreStep::Size(size));
+        }
+    }
+
+    if maximize {
+        steps.push(RestoreStep::Maximize);
+    }
+
+    if flags.contains(StateFlags::FULLSCREEN) {
+        steps.push(RestoreStep::Fullscreen(state.fullscreen));
+    }
+
+    (steps, deferred_size)
+}
+
+/// Returns the saved state of the given window, if it has one worth restoring.
+fn saved_state<'a>(
+    cache: &'a HashMap<String, WindowState>,
+    label: &str,
+    restore_validator: Option<&RestoreValidatorFn>,
+) -> Option<&'a WindowState> {
+    cache
+        .get(label)
+        .filter(|state| state != &&WindowState::default())
+        .filter(|state| restore_validator.map_or(true, |validator| validator(state)))
+}
+
 trait WindowExtInternal {
     fn update_state(&self, state: &mut WindowState, flags: StateFlags) -> tauri::Result<()>;
 }
@@ -323,6 +636,7 @@ impl<R: Runtime> WindowExtInternal for Window<R> {
             if size.width > 0 && size.height > 0 {
                 state.width = size.width;
                 state.height = size.height;
+                state.scale_factor = Some(self.scale_factor()?);
             }
         }
 
@@ -340,11 +654,14 @@ impl<R: Runtime> WindowExtInternal for Window<R> {
 pub struct Builder {
     denylist: HashSet<String>,
     filter_callback: Option<Box<FilterCallbackFn>>,
+    restore_validator: Option<Box<RestoreValidatorFn>>,
     skip_initial_state: HashSet<String>,
     state_flags: StateFlags,
     map_label: Option<Box<LabelMapperFn>>,
+    prefix_groups: Vec<String>,
     filename: Option<String>,
     dir: Option<PathBuf>,
+    save_policy: Option<SavePolicy>,
 }
 
 impl Builder {
@@ -388,6 +705,21 @@ impl Builder {
         self
     }
 
+    /// Sets a callback to validate a saved window state before restoring it.
+    /// Return `true` to restore the state, or `false` to ignore it and keep the window defaults.
+    ///
+    /// ```rust
+    /// tauri_plugin_window_state::Builder::new()
+    ///     .with_restore_validator(|state| state.width() >= 200 && state.height() >= 200);
+    /// ```
+    pub fn with_restore_validator<F>(mut self, restore_validator: F) -> Self
+    where
+        F: Fn(&WindowState) -> bool + Send + Sync + 'static,
+    {
+        self.restore_validator = Some(Box::new(restore_validator));
+        self
+    }
+
     /// Adds the given window label to a list of windows to skip initial state restore.
     pub fn skip_initial_state(mut self, label: &str) -> Self {
         self.skip_initial_state.insert(label.into());
@@ -405,11 +737,79 @@ impl Builder {
         self
     }
 
+    /// Makes all windows whose label starts with `prefix` share the same state, saved under the `prefix` label.
+    ///
+    /// Takes precedence over [`Self::map_label`] for the matching windows.
+    /// Windows in the [denylist](Self::with_denylist) or excluded by the [filter](Self::with_filter)
+    /// are not tracked, whether they match by their own label or by the `prefix`.
+    ///
+    /// ```rust
+    /// // `doc-1`, `doc-2`, ... all restore the state of the last closed document window
+    /// tauri_plugin_window_state::Builder::new()
+    ///     .group_by_prefix("doc-");
+    /// ```
+    pub fn group_by_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix_groups.push(prefix.into());
+        self
+    }
+
+    /// Also saves the window states to disk while the app runs, not only when it exits,
+    /// so a crash loses at most `max_interval` of changes.
+    ///
+    /// The states are saved once the windows stopped moving or resizing for `debounce`,
+    /// or `max_interval` after the first change not saved yet if they keep changing.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use tauri_plugin_window_state::SavePolicy;
+    ///
+    /// tauri_plugin_window_state::Builder::new().save_policy(SavePolicy {
+    ///     debounce: Duration::from_millis(500),
+    ///     max_interval: Duration::from_secs(5),
+    /// });
+    /// ```
+    pub fn save_policy(mut self, save_policy: SavePolicy) -> Self {
+        self.save_policy.replace(save_policy);
+        self
+    }
+
+    fn label_mapper(
+        prefix_groups: Vec<String>,
+        map_label: Option<Box<LabelMapperFn>>,
+    ) -> Option<Box<LabelMapperFn>> {
+        if prefix_groups.is_empty() {
+            return map_label;
+        }
+
+        Some(Box::new(move |label| {
+            match prefix_groups
+                .iter()
+                .find(|prefix| label.starts_with(prefix.as_str()))
+            {
+                Some(prefix) => &label[..prefix.len()],
+                None => map_label.as_ref().map_or(label, |map| map(label)),
+            }
+        }))
+    }
+
     pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
         let state_flags = self.state_flags;
         let filename = self.filename.unwrap_or_else(|| DEFAULT_FILENAME.into());
         let dir = self.dir;
-        let map_label = self.map_label;
+        let map_label = Self::label_mapper(self.prefix_groups.clone(), self.map_label);
+        let restore_validator = self.restore_validator;
+        let save_policy = self.save_policy;
+        let tracking = Arc::new(WindowTracking {
+            denylist: self.denylist,
+            filter_callback: self.filter_callback,
+            prefix_groups: self.prefix_groups,
+            skip_initial_state: self.skip_initial_state,
+            state_flags,
+        });
+        // windows that became ready before the plugin was set up, e.g. created in the setup of a plugin
+        // registered before this one, are tracked once the saved states are loaded
+        let pending_windows = Arc::new(Mutex::new(Vec::<Window<R>>::new()));
+        let (tracking_, pending_windows_) = (tracking.clone(), pending_windows.clone());
 
         PluginBuilder::new("window-state")
             .invoke_handler(tauri::generate_handler![
@@ -419,131 +819,203 @@ impl Builder {
                 cmd::directory
             ])
             .setup(move |app, _api| {
-                let cache =
-                    load_saved_window_states(app, &filename, dir.as_ref()).unwrap_or_default();
-                app.manage(WindowStateCache(Arc::new(Mutex::new(cache))));
-                app.manage(RestoringWindowState(Mutex::new(())));
-                app.manage(PluginState {
+                let plugin_state = PluginState {
                     state_flags,
                     filename,
                     dir,
+                    profile: Default::default(),
                     map_label,
-                });
+                    restore_validator,
+                    tracking: tracking_.clone(),
+                    autosave: save_policy
+                        .map(|policy| Autosave::new(policy).spawn(app.clone(), state_flags)),
+                };
+                let cache =
+                    load_saved_window_states(&plugin_state.dir(app).join(plugin_state.filename()))
+                        .unwrap_or_default();
+                app.manage(WindowStateCache(Arc::new(Mutex::new(cache))));
+                app.manage(RestoringWindowState(Mutex::new(())));
+                app.manage(DeferredWindowSizes(Default::default()));
+                app.manage(plugin_state);
+                for window in pending_windows_.lock().unwrap().drain(..) {
+                    tracking_.track(&window);
+                }
                 Ok(())
             })
             .on_window_ready(move |window| {
-                let plugin_state = window.app_handle().state::<PluginState>();
-                let label = plugin_state
-                    .map_label
-                    .as_ref()
-                    .map(|map| map(window.label()))
-                    .unwrap_or_else(|| window.label());
-
-                // Check deny list names
-                if self.denylist.contains(label) {
-                    return;
+                let mut pending_windows = pending_windows.lock().unwrap();
+                if window.try_state::<PluginState>().is_some() {
+                    tracking.track(&window);
+                } else {
+                    pending_windows.push(window);
                 }
-
-                // Check deny list callback
-                if let Some(filter_callback) = &self.filter_callback {
-                    // Don't save the state if the callback returns false
-                    if !filter_callback(label) {
-                        return;
-                    }
+            })
+            .on_event(move |app, event| {
+                if let RunEvent::Exit = event {
+                    let _ = app.save_window_state(state_flags);
                 }
+            })
+            .build()
+    }
+}
 
-                if !self.skip_initial_state.contains(label) {
-                    let _ = window.restore_state(state_flags);
-                }
+/// Restores and tracks the state of the windows not excluded from tracking.
+struct WindowTracking {
+    denylist: HashSet<String>,
+    filter_callback: Option<Box<FilterCallbackFn>>,
+    prefix_groups: Vec<String>,
+    skip_initial_state: HashSet<String>,
+    state_flags: StateFlags,
+}
 
-                let cache = window.state::<WindowStateCache>();
-                let cache = cache.0.clone();
-                let label = label.to_string();
-                let window_clone = window.clone();
+impl WindowTracking {
+    /// Whether the window with the given label, saved under `mapped_label`, is excluded from tracking.
+    fn excludes(&self, label: &str, mapped_label: &str) -> bool {
+        is_excluded(
+            &self.denylist,
+            self.filter_callback.as_deref(),
+            &self.prefix_groups,
+            label,
+            mapped_label,
+        )
+    }
 
-                // insert a default state if this window should be tracked and
-                // the disk cache doesn't have a state for it
-                {
-                    cache
-                        .lock()
-                        .unwrap()
-                        .entry(label.clone())
-                        .or_insert_with(WindowState::default);
-                }
+    fn track<R: Runtime>(&self, window: &Window<R>) {
+        let state_flags = self.state_flags;
+        let plugin_state = window.app_handle().state::<PluginState>();
+        let label = plugin_state
+            .map_label
+            .as_ref()
+            .map(|map| map(window.label()))
+            .unwrap_or_else(|| window.label());
 
-                window.on_window_event(move |e| match e {
-                    WindowEvent::CloseRequested { .. } => {
-                        let mut c = cache.lock().unwrap();
-                        if let Some(state) = c.get_mut(&label) {
-                            let _ = window_clone.update_state(state, state_flags);
-                        }
-                    }
+        // Check deny list names and callback
+        if self.excludes(window.label(), label) {
+            return;
+        }
 
-                    WindowEvent::Moved(position) if state_flags.contains(StateFlags::POSITION) => {
-                        if window_clone
-                            .state::<RestoringWindowState>()
+        if !self.skip_initial_state.contains(label) {
+            let _ = window.restore_state(state_flags);
+        }
+
+        let cache = window.state::<WindowStateCache>();
+        let cache = cache.0.clone();
+        let label = label.to_string();
+        let window_clone = window.clone();
+        let autosave = plugin_state.autosave.clone();
+        let changed = move || {
+            if let Some(autosave) = &autosave {
+                let _ = autosave.send(Instant::now());
+            }
+        };
+
+        // insert the current state if this window should be tracked and
+        // the disk cache doesn't have a state for it, e.g. if its initial state is skipped,
+        // the event handlers below also recreate it in case it was dropped from the cache in the meantime
+        {
+            let mut c = cache.lock().unwrap();
+            if !c.contains_key(&label) {
+                let mut state = WindowState::default();
+                let _ = window.update_state(&mut state, state_flags);
+                c.insert(label.clone(), state);
+            }
+        }
+
+        // whether the window was maximized on the last resize, so the size deferred by
+        // `restore_state` is only applied when the window is unmaximized
+        let was_maximized = AtomicBool::new(window.is_maximized().unwrap_or_default());
+
+        window.on_window_event(move |e| match e {
+            WindowEvent::CloseRequested { .. } => {
+                let mut c = cache.lock().unwrap();
+                let state = c.entry(label.clone()).or_default();
+                let _ = window_clone.update_state(state, state_flags);
+                changed();
+            }
+
+            WindowEvent::Moved(position) if state_flags.contains(StateFlags::POSITION) => {
+                if window_clone
+                    .state::<RestoringWindowState>()
+                    .0
+                    .try_lock()
+                    .is_ok()
+                    && !window_clone.is_minimized().unwrap_or_default()
+                {
+                    let mut c = cache.lock().unwrap();
+                    let state = c.entry(label.clone()).or_default();
+                    state.prev_x = state.x;
+                    state.prev_y = state.y;
+
+                    state.x = position.x;
+                    state.y = position.y;
+                    changed();
+                }
+            }
+            WindowEvent::Resized(size) if state_flags.contains(StateFlags::SIZE) => {
+                let maximized = window_clone.is_maximized().unwrap_or_default();
+                let unmaximized = was_maximized.swap(maximized, Ordering::Relaxed) && !maximized;
+
+                if window_clone
+                    .state::<RestoringWindowState>()
+                    .0
+                    .try_lock()
+                    .is_ok()
+                {
+                    // TODO: Remove once https://github.com/tauri-apps/tauri/issues/5812 is resolved.
+                    let is_maximized = if cfg!(target_os = "macos")
+                        && (!window_clone.is_decorated().unwrap_or_default()
+                            || !window_clone.is_resizable().unwrap_or_default())
+                    {
+                        false
+                    } else {
+                        window_clone.is_maximized().unwrap_or_default()
+                    };
+
+                    // apply the saved size of a window restored as maximized once it is unmaximized,
+                    // it is dropped on any other resize of the unmaximized window so it is never applied late
+                    if !maximized && !window_clone.is_minimized().unwrap_or_default() {
+                        let deferred_size = window_clone
+                            .state::<DeferredWindowSizes>()
                             .0
-                            .try_lock()
-                            .is_ok()
-                            && !window_clone.is_minimized().unwrap_or_default()
-                        {
-                            let mut c = cache.lock().unwrap();
-                            if let Some(state) = c.get_mut(&label) {
-                                state.prev_x = state.x;
-                                state.prev_y = state.y;
-
-                                state.x = position.x;
-                                state.y = position.y;
-                            }
+                            .lock()
+                            .unwrap()
+                            .remove(window_clone.label());
+                        if let (true, Some(size)) = (unmaximized, deferred_size) {
+                            let _ = window_clone.set_size(size);
+                            return;
                         }
                     }
-                    WindowEvent::Resized(size) if state_flags.contains(StateFlags::SIZE) => {
-                        if window_clone
-                            .state::<RestoringWindowState>()
-                            .0
-                            .try_lock()
-                            .is_ok()
-                        {
-                            // TODO: Remove once https://github.com/tauri-apps/tauri/issues/5812 is resolved.
-                            let is_maximized = if cfg!(target_os = "macos")
-                                && (!window_clone.is_decorated().unwrap_or_default()
-                                    || !window_clone.is_resizable().unwrap_or_default())
-                            {
-                                false
-                            } else {
-                                window_clone.is_maximized().unwrap_or_default()
-                            };
-
-                            if !window_clone.is_minimized().unwrap_or_default() && !is_maximized {
-                                let mut c = cache.lock().unwrap();
-                                if let Some(state) = c.get_mut(&label) {
-                                    state.width = size.width;
-                                    state.height = size.height;
-                                }
-                            }
-                        }
+
+                    if !window_clone.is_minimized().unwrap_or_default() && !is_maximized {
+                        let mut c = cache.lock().unwrap();
+                        let state = c.entry(label.clone()).or_default();
+                        state.width = size.width;
+                        state.height = size.height;
+                        state.scale_factor = window_clone.scale_factor().ok();
+                        changed();
                     }
-                    _ => {}
-                });
-            })
-            .on_event(move |app, event| {
-                if let RunEvent::Exit = event {
-                    let _ = app.save_window_state(state_flags);
                 }
-            })
-            .build()
+            }
+            _ => {}
+        });
     }
 }
 
-fn load_saved_window_states<R: Runtime>(
-    app: &AppHandle<R>,
-    filename: &String,
-    dir: Option<&PathBuf>,
-) -> Result<HashMap<String, WindowState>> {
-    let app_dir = dir
-        .map(|dir| dir.clone())
-        .unwrap_or_else(|| app.path().app_config_dir().unwrap_or_default());
-    let state_path = app_dir.join(filename);
+/// Writes `contents` to a temporary file next to `path` then renames it,
+/// so `path` is never left partially written.
+fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut file = std::fs::File::create(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::rename(tmp_path, path)
+}
+
+fn load_saved_window_states(state_path: &Path) -> Result<HashMap<String, WindowState>> {
     let file = std::fs::File::open(state_path)?;
     let reader = BufReader::new(file);
     let states = serde_json::from_reader(reader)?;
@@ -577,3 +1049,328 @@ impl MonitorExt for Monitor {
         .any(|(x, y)| x >= left && x < right && y >= top && y < bottom)
     }
 }
+
+/// Whether the window with the given label, saved under `mapped_label`, is excluded from tracking.
+///
+/// Windows grouped by prefix are also excluded by their own label,
+/// the others are only matched by the label their state is saved under.
+fn is_excluded(
+    denylist: &HashSet<String>,
+    filter_callback: Option<&FilterCallbackFn>,
+    prefix_groups: &[String],
+    label: &str,
+    mapped_label: &str,
+) -> bool {
+    let excluded = |label: &str| {
+        denylist.contains(label)
+            || filter_callback.is_some_and(|filter_callback| !filter_callback(label))
+    };
+    let grouped = prefix_groups
+        .iter()
+        .any(|prefix| label.starts_with(prefix.as_str()));
+    excluded(mapped_label) || (grouped && excluded(label))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restore_validator_rejects_tiny_size() {
+        let mut cache = HashMap::new();
+        cache.insert(
+            "main".to_string(),
+            WindowState {
+                width: 10,
+                height: 10,
+                ..Default::default()
+            },
+        );
+
+        let validator: Box<RestoreValidatorFn> =
+            Box::new(|state| state.width() >= 200 && state.height() >= 200);
+
+        assert!(saved_state(&cache, "main", None).is_some());
+        assert!(saved_state(&cache, "main", Some(&*validator)).is_none());
+    }
+
+    #[test]
+    fn prefixed_windows_share_state() {
+        let builder = Builder::new()
+            .with_denylist(&["doc-secret", "other"])
+            .map_label(|label| if label == "other" { "main" } else { label })
+            .group_by_prefix("doc-");
+        let map = Builder::label_mapper(builder.prefix_groups.clone(), None).unwrap();
+
+        let mut cache = HashMap::new();
+        cache.insert(
+            "doc-".to_string(),
+            WindowState {
+                width: 800,
+                ..Default::default()
+            },
+        );
+
+        for label in ["doc-1", "doc-2", "doc-report"] {
+            assert_eq!(map(label), "doc-");
+            assert!(!is_excluded(
+                &builder.denylist,
+                None,
+                &builder.prefix_groups,
+                label,
+                map(label)
+            ));
+            assert_eq!(saved_state(&cache, map(label), None).unwrap().width, 800);
+        }
+        assert_eq!(map("main"), "main");
+        assert!(is_excluded(
+            &builder.denylist,
+            None,
+            &builder.prefix_groups,
+            "doc-secret",
+            map("doc-secret")
+        ));
+
+        let map = Builder::label_mapper(builder.prefix_groups.clone(), builder.map_label).unwrap();
+        assert_eq!(map("doc-1"), "doc-");
+        assert_eq!(map("other"), "main");
+        // windows mapped with `map_label` are still only matched by their mapped label
+        assert!(!is_excluded(
+            &builder.denylist,
+            None,
+            &builder.prefix_groups,
+            "other",
+            map("other")
+        ));
+    }
+
+    #[test]
+    fn maximized_restore_defers_size() {
+        let state = WindowState {
+            width: 400,
+            height: 300,
+            x: 0,
+            y: 0,
+            prev_x: 100,
+            prev_y: 50,
+            maximized: true,
+            ..Default::default()
+        };
+
+        let (steps, deferred_size) = restore_steps(&state, StateFlags::all(), true, 1.0);
+
+        assert_eq!(
+            steps,
+            [
+                RestoreStep::Decorations(true),
+                RestoreStep::Position(PhysicalPosition::new(100, 50)),
+                RestoreStep::Maximize,
+                RestoreStep::Fullscreen(false),
+            ]
+        );
+        assert_eq!(deferred_size, Some(PhysicalSize::new(400, 300)));
+    }
+
+    #[test]
+    fn unmaximized_restore_sets_size() {
+        let state = WindowState {
+            width: 400,
+            height: 300,
+            ..Default::default()
+        };
+
+        let (steps, deferred_size) = restore_steps(&state, StateFlags::SIZE, false, 1.0);
+
+        assert_eq!(steps, [RestoreStep::Size(PhysicalSize::new(400, 300))]);
+        assert_eq!(deferred_size, None);
+    }
+
+    #[test]
+    fn restored_size_keeps_logical_size_across_scale_factors() {
+        let state = WindowState {
+            width: 1200,
+            height: 900,
+            scale_factor: Some(1.5),
+            ..Default::default()
+        };
+
+        assert_eq!(state.size_at(1.5), PhysicalSize::new(1200, 900));
+        assert_eq!(state.size_at(1.0), PhysicalSize::new(800, 600));
+        assert_eq!(state.size_at(2.0), PhysicalSize::new(1600, 1200));
+
+        let (steps, _) = restore_steps(&state, StateFlags::SIZE, false, 2.0);
+        assert_eq!(steps, [RestoreStep::Size(PhysicalSize::new(1600, 1200))]);
+
+        let state = WindowState {
+            maximized: true,
+            ..state
+        };
+        let (_, deferred_size) = restore_steps(&state, StateFlags::all(), true, 1.0);
+        assert_eq!(deferred_size, Some(PhysicalSize::new(800, 600)));
+    }
+
+    #[test]
+    fn states_without_scale_factor_restore_as_saved() {
+        let states: HashMap<String, WindowState> = serde_json::from_str(
+            r#"{"main":{"width":800,"height":600,"x":10,"y":20,"prev_x":0,"prev_y":0,"maximized":false,"visible":true,"decorated":true,"fullscreen":false}}"#,
+        )
+        .unwrap();
+        let state = &states["main"];
+
+        assert_eq!(state.scale_factor(), None);
+        assert_eq!(state.size_at(2.0), PhysicalSize::new(800, 600));
+    }
+
+    #[test]
+    fn restores_windows_created_after_setup() {
+        let dir = tempfile::tempdir().unwrap();
+        let saved = WindowState {
+            width: 800,
+            height: 600,
+            x: 100,
+            y: 200,
+            maximized: true,
+            ..Default::default()
+        };
+        std::fs::write(
+            dir.path().join(DEFAULT_FILENAME),
+            serde_json::to_vec(&HashMap::from([("late", &saved)])).unwrap(),
+        )
+        .unwrap();
+
+        let app = tauri::test::mock_builder()
+            .plugin(
+                Builder::new()
+                    .with_dir(dir.path())
+                    .skip_initial_state("skipped")
+                    .build(),
+            )
+            .build(tauri::test::mock_context(tauri::test::noop_assets()))
+            .unwrap();
+
+        // the windows are created long after the plugin was set up
+        for label in ["late", "fresh", "skipped"] {
+            tauri::WebviewWindowBuilder::new(&app, label, Default::default())
+                .build()
+                .unwrap();
+        }
+
+        // the plugin restored the saved maximized window, deferring its size until it is unmaximized
+        assert_eq!(
+            app.state::<DeferredWindowSizes>()
+                .0
+                .lock()
+                .unwrap()
+                .get("late"),
+            Some(&PhysicalSize::new(800, 600))
+        );
+
+        let cache = app.state::<WindowStateCache>();
+        let cache = cache.0.lock().unwrap();
+        assert_eq!(cache.get("late"), Some(&saved));
+        // the windows without saved state get an entry with their current state
+        // instead of the default one, the mock runtime reports undecorated windows
+        for label in ["fresh", "skipped"] {
+            assert!(!cache.get(label).unwrap().decorated);
+        }
+    }
+
+    #[test]
+    fn profile_filenames() {
+        assert_eq!(
+            profile_filename(DEFAULT_FILENAME, "work"),
+            ".window-state.work.json"
+        );
+        assert_eq!(profile_filename("layout", "work"), "layout.work");
+        assert_eq!(profile_filename(".layout", "work"), ".layout.work");
+    }
+
+    #[test]
+    fn profiles_keep_independent_layouts() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let layout = |width| WindowState {
+            width,
+            height: 600,
+            ..Default::default()
+        };
+        let write = |filename: &str, width| {
+            std::fs::write(
+                dir.join(filename),
+                serde_json::to_vec(&HashMap::from([("main", layout(width))])).unwrap(),
+            )
+            .unwrap()
+        };
+        let read = |filename: &str| -> HashMap<String, WindowState> {
+            serde_json::from_slice(&std::fs::read(dir.join(filename)).unwrap()).unwrap()
+        };
+        let cached_width = |app: &tauri::App<tauri::test::MockRuntime>| {
+            app.state::<WindowStateCache>().0.lock().unwrap()["main"].width
+        };
+        write(DEFAULT_FILENAME, 800);
+        write(".window-state.work.json", 1024);
+
+        let app = tauri::test::mock_builder()
+            .plugin(
+                Builder::new()
+                    .with_dir(dir)
+                    .with_state_flags(StateFlags::SIZE)
+                    .build(),
+            )
+            .build(tauri::test::mock_context(tauri::test::noop_assets()))
+            .unwrap();
+        tauri::WebviewWindowBuilder::new(&app, "main", Default::default())
+            .build()
+            .unwrap();
+        assert_eq!(cached_width(&app), 800);
+
+        let handle = app.handle();
+        handle.set_profile(Some("work"), true).unwrap();
+        assert_eq!(handle.profile().as_deref(), Some("work"));
+        assert_eq!(handle.filename(), ".window-state.work.json");
+        assert_eq!(cached_width(&app), 1024);
+
+        // a resize in the work profile doesn't leak into the default one
+        app.state::<WindowStateCache>()
+            .0
+            .lock()
+            .unwrap()
+            .get_mut("main")
+            .unwrap()
+            .width = 1280;
+        handle.set_profile(None, false).unwrap();
+        assert_eq!(cached_width(&app), 800);
+        assert_eq!(read(".window-state.work.json")["main"].width, 1280);
+        assert_eq!(read(DEFAULT_FILENAME)["main"].width, 800);
+
+        assert!(matches!(
+            handle.set_profile(Some("../work"), false),
+            Err(Error::InvalidProfile(_))
+        ));
+    }
+
+    #[test]
+    fn autosave_cadence_under_moves() {
+        let mut autosave = Autosave::new(SavePolicy {
+            debounce: Duration::from_millis(500),
+            max_interval: Duration::from_secs(2),
+        });
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+
+        // a window moved every 100ms for 5s, then a single move after a pause
+        let moves = (0..50).map(|i| ms(i * 100)).chain([ms(8000)]);
+        let mut saves = Vec::new();
+        for at in moves {
+            while let Some(deadline) = autosave.deadline().filter(|deadline| *deadline <= at) {
+                saves.push(deadline);
+                autosave.saved();
+            }
+            autosave.changed(at);
+        }
+        saves.extend(autosave.deadline());
+
+        // saved every `max_interval` while moving, then `debounce` after the last moves
+        assert_eq!(saves, [ms(2000), ms(4000), ms(5400), ms(8500)]);
+    }
+}